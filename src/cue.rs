@@ -0,0 +1,46 @@
+use crate::{item::Item, time::Time};
+
+/// A timed piece of text
+///
+/// Allows to write code working with subtitles from different crates.
+///
+/// ```
+/// use srtparse::{Cue, Item};
+/// use std::time::Duration;
+///
+/// fn total_duration<C: Cue>(cues: &[C]) -> Duration {
+///     cues.iter()
+///         .map(|cue| cue.end().into_duration().saturating_sub(cue.start().into_duration()))
+///         .sum()
+/// }
+///
+/// let items: Vec<Item> = srtparse::from_str(
+///     "1\n00:00:01,000 --> 00:00:02,500\nHello\n\n2\n00:00:03,000 --> 00:00:04,000\nWorld",
+/// )
+/// .unwrap();
+/// assert_eq!(total_duration(&items), Duration::from_millis(2500));
+/// ```
+pub trait Cue {
+    /// Returns the time when the cue should appear
+    fn start(&self) -> Time;
+
+    /// Returns the time when the cue should disappear
+    fn end(&self) -> Time;
+
+    /// Returns text of the cue
+    fn text(&self) -> &str;
+}
+
+impl Cue for Item {
+    fn start(&self) -> Time {
+        self.start_time
+    }
+
+    fn end(&self) -> Time {
+        self.end_time
+    }
+
+    fn text(&self) -> &str {
+        &self.text
+    }
+}
@@ -0,0 +1,719 @@
+use crate::{item::Item, stats::gap, time::Time, validate::is_zero_duration};
+use alloc::{string::String, vec::Vec};
+use core::time::Duration;
+
+/// Assigns sequential positions to subtitles starting from 1
+pub fn renumber(items: &mut [Item]) {
+    for (idx, item) in items.iter_mut().enumerate() {
+        item.pos = idx + 1;
+    }
+}
+
+/// Shifts subtitles by the given number of milliseconds
+///
+/// Negative offset moves subtitles earlier, resulting times are clamped at zero.
+pub fn shift(items: &mut [Item], offset: i64) {
+    for item in items.iter_mut() {
+        item.start_time = shift_time(item.start_time, offset);
+        item.end_time = shift_time(item.end_time, offset);
+    }
+}
+
+/// Shifts subtitles within `from_index..=to_index` range by the given number of milliseconds
+///
+/// Indices are 0-based slice indices rather than subtitle positions, subtitles outside the range are left untouched.
+/// A range exceeding the slice is truncated to its end.
+pub fn shift_range(items: &mut [Item], from_index: usize, to_index: usize, offset: i64) {
+    let to_index = to_index.min(items.len().saturating_sub(1));
+    if let Some(items) = items.get_mut(from_index..=to_index) {
+        shift(items, offset);
+    }
+}
+
+/// Shifts subtitles so that the one at `index` starts at `target` time
+///
+/// Index starts from 0, all subtitles are shifted by the same offset with times clamped at zero.
+/// Nothing is changed when index is out of range.
+pub fn anchor(items: &mut [Item], index: usize, target: Time) {
+    if let Some(item) = items.get(index) {
+        let offset = target.diff(&item.start_time);
+        shift(items, offset);
+    }
+}
+
+fn shift_time(time: Time, offset: i64) -> Time {
+    let delta = Duration::from_millis(offset.unsigned_abs());
+    if offset < 0 {
+        time - delta
+    } else {
+        time + delta
+    }
+}
+
+/// Shifts subtitles so that the first one starts at zero
+pub fn rebase_to_zero(items: &mut [Item]) {
+    if let Some(first) = items.first() {
+        let offset = first.start_time.into_duration();
+        for item in items.iter_mut() {
+            item.start_time = item.start_time - offset;
+            item.end_time = item.end_time - offset;
+        }
+    }
+}
+
+/// Extends subtitles to the start of the next one when the gap between them is shorter than `max_gap`
+pub fn fill_small_gaps(items: &mut [Item], max_gap: Duration) {
+    for idx in 1..items.len() {
+        let gap = gap(&items[idx - 1], &items[idx]);
+        if !gap.is_zero() && gap < max_gap {
+            items[idx - 1].end_time = items[idx].start_time;
+        }
+    }
+}
+
+/// A gap kept between subtitles when extending them
+const MIN_GAP: Duration = Duration::from_millis(80);
+
+/// Extends subtitles displayed for less than `min` by moving their end time
+///
+/// A subtitle is never extended beyond 80 milliseconds before the start of the next one,
+/// so it may remain shorter than `min`.
+pub fn enforce_min_duration(items: &mut [Item], min: Duration) {
+    for idx in 0..items.len() {
+        let start_time = items[idx].start_time.into_duration();
+        let end_time = items[idx].end_time.into_duration();
+        if end_time.saturating_sub(start_time) >= min {
+            continue;
+        }
+        let mut new_end_time = start_time + min;
+        if let Some(next) = items.get(idx + 1) {
+            new_end_time = new_end_time.min(next.start_time.into_duration().saturating_sub(MIN_GAP));
+        }
+        if new_end_time > end_time {
+            items[idx].end_time = Time::from_duration(new_end_time);
+        }
+    }
+}
+
+/// Inserts a subtitle keeping subtitles sorted by start time
+///
+/// Subtitles are expected to be sorted already, a subtitle is inserted after ones with the same start time.
+/// Subtitles are renumbered afterwards.
+pub fn insert_sorted(items: &mut Vec<Item>, item: Item) {
+    let idx = items.partition_point(|x| x.start_time <= item.start_time);
+    items.insert(idx, item);
+    renumber(items);
+}
+
+/// Merges consecutive subtitles with identical text into a single one
+///
+/// Merged subtitle starts with the first subtitle and ends with the latest end time among merged ones.
+/// Subtitles are renumbered afterwards.
+pub fn coalesce_identical(items: &mut Vec<Item>) {
+    items.dedup_by(|next, prev| {
+        if next.text == prev.text {
+            if next.end_time.into_duration() > prev.end_time.into_duration() {
+                prev.end_time = next.end_time;
+            }
+            true
+        } else {
+            false
+        }
+    });
+    renumber(items);
+}
+
+/// Removes consecutive duplicates of subtitles
+///
+/// Subtitles are duplicates when their start time, end time, text and coordinates are equal,
+/// positions and labels are not compared.
+/// Subtitles are renumbered afterwards.
+pub fn dedup(items: &mut Vec<Item>) {
+    items.dedup_by(|next, prev| {
+        next.start_time == prev.start_time
+            && next.end_time == prev.end_time
+            && next.text == prev.text
+            && next.coordinates == prev.coordinates
+    });
+    renumber(items);
+}
+
+/// Synchronizes subtitles by two anchor points
+///
+/// Times are mapped linearly, so that `a_old` becomes `a_new` and `b_old` becomes `b_new`,
+/// resulting times are rounded to the nearest millisecond and clamped at zero.
+/// When `a_old` equals `b_old`, subtitles are shifted by `a_new - a_old`.
+pub fn resync(items: &mut [Item], a_old: Time, a_new: Time, b_old: Time, b_new: Time) {
+    let a_old = i128::from(a_old.total_milliseconds());
+    let a_new = i128::from(a_new.total_milliseconds());
+    let mut num = i128::from(b_new.total_milliseconds()) - a_new;
+    let mut den = i128::from(b_old.total_milliseconds()) - a_old;
+    if den == 0 {
+        num = 1;
+        den = 1;
+    } else if den < 0 {
+        num = -num;
+        den = -den;
+    }
+    let map = |time: Time| {
+        let offset = (i128::from(time.total_milliseconds()) - a_old) * num;
+        let milliseconds = a_new + (2 * offset + den).div_euclid(2 * den);
+        Time::from_duration(Duration::from_millis(milliseconds.clamp(0, i128::from(u64::MAX)) as u64))
+    };
+    for item in items.iter_mut() {
+        item.start_time = map(item.start_time);
+        item.end_time = map(item.end_time);
+    }
+}
+
+/// Removes subtitles which start time is equal to end time
+///
+/// Subtitles are renumbered afterwards.
+pub fn drop_zero_duration(items: &mut Vec<Item>) {
+    items.retain(|item| !is_zero_duration(item));
+    renumber(items);
+}
+
+/// Splits subtitles into two parts at the given time
+///
+/// Subtitles starting before `at` go to the first part, including the ones that end after `at`.
+/// Times of the second part are rebased so that `at` becomes zero and positions start from 1.
+pub fn split_at_time(items: Vec<Item>, at: Time) -> (Vec<Item>, Vec<Item>) {
+    let at = at.into_duration();
+    let (first, mut second): (Vec<Item>, Vec<Item>) =
+        items.into_iter().partition(|item| item.start_time.into_duration() < at);
+    for item in second.iter_mut() {
+        item.start_time = item.start_time - at;
+        item.end_time = item.end_time - at;
+    }
+    renumber(&mut second);
+    (first, second)
+}
+
+/// Re-wraps text of subtitles so that no line exceeds `max_chars` characters
+///
+/// Lines which already fit are left untouched along with existing line breaks.
+/// Longer lines are wrapped on word boundaries,
+/// a word longer than `max_chars` occupies a line on its own.
+/// Length is measured in characters, not bytes.
+pub fn reflow(items: &mut [Item], max_chars: usize) {
+    for item in items.iter_mut() {
+        if item.text.lines().all(|line| line.chars().count() <= max_chars) {
+            continue;
+        }
+        let mut text = String::new();
+        for (idx, line) in item.text.lines().enumerate() {
+            if idx > 0 {
+                text.push('\n');
+            }
+            if line.chars().count() <= max_chars {
+                text.push_str(line);
+            } else {
+                wrap_line(&mut text, line, max_chars);
+            }
+        }
+        item.text = text;
+    }
+}
+
+fn wrap_line(text: &mut String, line: &str, max_chars: usize) {
+    let mut line_len = 0;
+    for word in line.split_whitespace() {
+        let word_len = word.chars().count();
+        if line_len > 0 {
+            if line_len + 1 + word_len > max_chars {
+                text.push('\n');
+                line_len = 0;
+            } else {
+                text.push(' ');
+                line_len += 1;
+            }
+        }
+        text.push_str(word);
+        line_len += word_len;
+    }
+}
+
+/// Splits subtitles which text exceeds `max_chars` characters into several subtitles
+///
+/// Text is split on word boundaries preferring the ones after a sentence end, lines are joined with spaces.
+/// Display time is divided between parts proportionally to their length so parts follow each other without gaps.
+/// Length is measured in characters of text put on a single line, markup tags are not treated specially.
+/// Subtitles are renumbered afterwards.
+pub fn split_long_cues(items: &mut Vec<Item>, max_chars: usize) {
+    let mut result = Vec::with_capacity(items.len());
+    for item in items.drain(..) {
+        if item.single_line().chars().count() <= max_chars {
+            result.push(item);
+            continue;
+        }
+        let parts = split_text(&item.text, max_chars);
+        let total = parts.iter().map(|part| part.chars().count()).sum::<usize>() as u128;
+        let start = item.start_time.into_duration().as_millis();
+        let window = item.duration().as_millis();
+        let count = parts.len();
+        let mut chars = 0;
+        let mut part_start = item.start_time;
+        for (idx, text) in parts.into_iter().enumerate() {
+            chars += text.chars().count() as u128;
+            let part_end = if idx + 1 == count {
+                item.end_time
+            } else {
+                Time::from_duration(Duration::from_millis((start + window * chars / total) as u64))
+            };
+            result.push(Item {
+                pos: item.pos,
+                start_time: part_start,
+                end_time: part_end,
+                text,
+                label: if idx == 0 { item.label.clone() } else { None },
+                coordinates: item.coordinates,
+            });
+            part_start = part_end;
+        }
+    }
+    *items = result;
+    renumber(items);
+}
+
+fn split_text(text: &str, max_chars: usize) -> Vec<String> {
+    let joined_len = |words: &[&str]| words.iter().map(|word| word.chars().count() + 1).sum::<usize>();
+    let mut parts = Vec::new();
+    let mut words: Vec<&str> = Vec::new();
+    for word in text.split_whitespace() {
+        while !words.is_empty() && joined_len(&words) + word.chars().count() > max_chars {
+            let at = words
+                .iter()
+                .rposition(|word| word.ends_with(['.', '!', '?']))
+                .map_or(words.len(), |idx| idx + 1);
+            let rest = words.split_off(at);
+            parts.push(words.join(" "));
+            words = rest;
+        }
+        words.push(word);
+    }
+    if !words.is_empty() {
+        parts.push(words.join(" "));
+    }
+    parts
+}
+
+/// Removes subtitles displayed entirely within `start..=end` range
+///
+/// When `close_gap` is `true`, the range is cut out of the timeline:
+/// times after `end` are shifted earlier by the duration of the range and times within it become `start`,
+/// so subtitles partially displayed within the range are trimmed and nothing starts to overlap.
+/// Subtitles are renumbered afterwards.
+pub fn remove_range(items: &mut Vec<Item>, start: Time, end: Time, close_gap: bool) {
+    let start = start.into_duration();
+    let end = end.into_duration();
+    items.retain(|item| item.start_time.into_duration() < start || item.end_time.into_duration() > end);
+    if close_gap {
+        let span = end.saturating_sub(start);
+        let cut = |time: Time| {
+            let value = time.into_duration();
+            if value < start {
+                time
+            } else if value <= end {
+                Time::from_duration(start)
+            } else {
+                Time::from_duration(value - span)
+            }
+        };
+        for item in items.iter_mut() {
+            item.start_time = cut(item.start_time);
+            item.end_time = cut(item.end_time);
+        }
+    }
+    renumber(items);
+}
+
+/// Trims subtitles to end no later than `max_end`
+///
+/// Subtitles starting at or after `max_end` are removed,
+/// subtitles straddling it get their end time clamped to `max_end`.
+/// Subtitles are renumbered afterwards.
+pub fn clamp_to(items: &mut Vec<Item>, max_end: Time) {
+    items.retain(|item| item.start_time.into_duration() < max_end.into_duration());
+    for item in items.iter_mut() {
+        if item.end_time.into_duration() > max_end.into_duration() {
+            item.end_time = max_end;
+        }
+    }
+    renumber(items);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn time(seconds: u64) -> Time {
+        Time {
+            hours: 0,
+            minutes: 0,
+            seconds,
+            milliseconds: 0,
+        }
+    }
+
+    fn item(pos: usize, start: u64, end: u64, text: &str) -> Item {
+        Item {
+            pos,
+            start_time: time(start),
+            end_time: time(end),
+            text: String::from(text),
+            label: None,
+            coordinates: None,
+        }
+    }
+
+    #[test]
+    fn renumber_items() {
+        let mut items = vec![item(5, 1, 2, "a"), item(7, 3, 4, "b")];
+        renumber(&mut items);
+        assert_eq!(items, vec![item(1, 1, 2, "a"), item(2, 3, 4, "b")]);
+    }
+
+    #[test]
+    fn coalesce_identical_items() {
+        let mut items = vec![
+            item(1, 1, 2, "hello"),
+            item(2, 3, 4, "…"),
+            item(3, 4, 5, "…"),
+            item(4, 5, 7, "…"),
+            item(5, 8, 9, "bye"),
+        ];
+        coalesce_identical(&mut items);
+        assert_eq!(
+            items,
+            vec![item(1, 1, 2, "hello"), item(2, 3, 7, "…"), item(3, 8, 9, "bye")]
+        );
+
+        let mut items = vec![item(1, 1, 6, "long"), item(2, 2, 3, "long")];
+        coalesce_identical(&mut items);
+        assert_eq!(items, vec![item(1, 1, 6, "long")]);
+    }
+
+    #[test]
+    fn split_at_time_clean() {
+        let items = vec![item(1, 1, 2, "a"), item(2, 3, 4, "b"), item(3, 6, 8, "c")];
+        let (first, second) = split_at_time(items, time(5));
+        assert_eq!(first, vec![item(1, 1, 2, "a"), item(2, 3, 4, "b")]);
+        assert_eq!(second, vec![item(1, 1, 3, "c")]);
+    }
+
+    #[test]
+    fn split_at_time_straddling() {
+        let items = vec![item(1, 1, 2, "a"), item(2, 3, 6, "b"), item(3, 7, 8, "c")];
+        let (first, second) = split_at_time(items, time(5));
+        assert_eq!(first, vec![item(1, 1, 2, "a"), item(2, 3, 6, "b")]);
+        assert_eq!(second, vec![item(1, 2, 3, "c")]);
+    }
+
+    #[test]
+    fn reflow_long_line() {
+        let mut items = vec![item(
+            1,
+            1,
+            2,
+            "The war had all but ground to a halt in the blink of an eye.",
+        )];
+        reflow(&mut items, 20);
+        assert_eq!(
+            items[0].text,
+            "The war had all but\nground to a halt in\nthe blink of an eye."
+        );
+    }
+
+    #[test]
+    fn reflow_existing_breaks() {
+        let mut items = vec![
+            item(1, 1, 2, "Short line\nanother one"),
+            item(2, 3, 4, "Short\nand a much longer line\nfits too"),
+            item(3, 5, 6, "Война закончилась в мгновение ока."),
+        ];
+        reflow(&mut items, 16);
+        assert_eq!(items[0].text, "Short line\nanother one");
+        assert_eq!(items[1].text, "Short\nand a much\nlonger line\nfits too");
+        assert_eq!(items[2].text, "Война\nзакончилась в\nмгновение ока.");
+    }
+
+    #[test]
+    fn remove_range_keep_gap() {
+        let mut items = vec![
+            item(1, 1, 2, "a"),
+            item(2, 3, 4, "b"),
+            item(3, 5, 6, "c"),
+            item(4, 6, 9, "d"),
+            item(5, 10, 11, "e"),
+        ];
+        remove_range(&mut items, time(3), time(7), false);
+        assert_eq!(
+            items,
+            vec![item(1, 1, 2, "a"), item(2, 6, 9, "d"), item(3, 10, 11, "e")]
+        );
+    }
+
+    #[test]
+    fn remove_range_close_gap() {
+        let mut items = vec![
+            item(1, 1, 2, "a"),
+            item(2, 3, 4, "b"),
+            item(3, 5, 6, "c"),
+            item(4, 6, 9, "d"),
+            item(5, 10, 11, "e"),
+        ];
+        remove_range(&mut items, time(3), time(7), true);
+        assert_eq!(items, vec![item(1, 1, 2, "a"), item(2, 3, 5, "d"), item(3, 6, 7, "e")]);
+
+        let mut items = vec![item(1, 2, 4, "a"), item(2, 5, 6, "b"), item(3, 1, 9, "c")];
+        remove_range(&mut items, time(3), time(7), true);
+        assert_eq!(items, vec![item(1, 2, 3, "a"), item(2, 1, 5, "c")]);
+    }
+
+    #[test]
+    fn shift_items() {
+        let mut items = vec![item(1, 1, 2, "a"), item(2, 3, 4, "b")];
+        shift(&mut items, 2000);
+        assert_eq!(items, vec![item(1, 3, 4, "a"), item(2, 5, 6, "b")]);
+        shift(&mut items, -4000);
+        assert_eq!(items, vec![item(1, 0, 0, "a"), item(2, 1, 2, "b")]);
+    }
+
+    #[test]
+    fn fill_small_gaps_items() {
+        let mut items = vec![
+            item(1, 1, 2, "a"),
+            item(2, 3, 4, "b"),
+            item(3, 6, 7, "c"),
+            item(4, 7, 8, "d"),
+        ];
+        fill_small_gaps(&mut items, Duration::from_millis(1500));
+        assert_eq!(
+            items,
+            vec![
+                item(1, 1, 3, "a"),
+                item(2, 3, 4, "b"),
+                item(3, 6, 7, "c"),
+                item(4, 7, 8, "d"),
+            ]
+        );
+        fill_small_gaps(&mut items, Duration::from_millis(2001));
+        assert_eq!(items[1], item(2, 3, 6, "b"));
+    }
+
+    #[test]
+    fn enforce_min_duration_free() {
+        let mut items = vec![item(1, 1, 1, "a"), item(2, 5, 6, "b")];
+        enforce_min_duration(&mut items, Duration::from_millis(700));
+        assert_eq!(items[0].end_time.into_duration(), Duration::from_millis(1700));
+        assert_eq!(items[1], item(2, 5, 6, "b"));
+    }
+
+    #[test]
+    fn enforce_min_duration_capped() {
+        let mut items = vec![item(1, 1, 1, "a"), item(2, 2, 2, "b")];
+        enforce_min_duration(&mut items, Duration::from_secs(3));
+        assert_eq!(items[0].end_time.into_duration(), Duration::from_millis(1920));
+        assert_eq!(items[1].end_time.into_duration(), Duration::from_secs(5));
+    }
+
+    #[test]
+    fn dedup_items() {
+        let mut items = vec![
+            item(1, 1, 2, "a"),
+            item(2, 1, 2, "a"),
+            item(3, 1, 3, "a"),
+            item(4, 4, 5, "b"),
+        ];
+        dedup(&mut items);
+        assert_eq!(items, vec![item(1, 1, 2, "a"), item(2, 1, 3, "a"), item(3, 4, 5, "b")]);
+
+        let mut positioned = item(2, 1, 2, "a");
+        positioned.coordinates = Some(crate::item::Coordinates {
+            x1: 1,
+            x2: 2,
+            y1: 3,
+            y2: 4,
+        });
+        let mut items = vec![item(1, 1, 2, "a"), positioned.clone()];
+        dedup(&mut items);
+        assert_eq!(items, vec![item(1, 1, 2, "a"), positioned]);
+    }
+
+    #[test]
+    fn clamp_to_removes_items_past_limit() {
+        let mut items = vec![item(1, 1, 2, "a"), item(2, 6, 7, "b"), item(3, 8, 9, "c")];
+        clamp_to(&mut items, time(5));
+        assert_eq!(items, vec![item(1, 1, 2, "a")]);
+
+        let mut items = vec![item(1, 1, 2, "a"), item(2, 5, 7, "b")];
+        clamp_to(&mut items, time(5));
+        assert_eq!(items, vec![item(1, 1, 2, "a")]);
+    }
+
+    #[test]
+    fn clamp_to_straddling_item() {
+        let mut items = vec![item(3, 1, 2, "a"), item(4, 4, 7, "b"), item(5, 8, 9, "c")];
+        clamp_to(&mut items, time(5));
+        assert_eq!(items, vec![item(1, 1, 2, "a"), item(2, 4, 5, "b")]);
+    }
+
+    #[test]
+    fn clamp_to_unnormalized_times() {
+        let mut items = vec![item(1, 1, 2, "a")];
+        items[0].end_time = Time {
+            hours: 0,
+            minutes: 0,
+            seconds: 100,
+            milliseconds: 0,
+        };
+        let max_end = Time {
+            hours: 0,
+            minutes: 1,
+            seconds: 30,
+            milliseconds: 0,
+        };
+        clamp_to(&mut items, max_end);
+        assert_eq!(items[0].end_time, max_end);
+
+        let mut items = vec![item(1, 1, 80, "a")];
+        clamp_to(&mut items, max_end);
+        assert_eq!(items[0].end_time, time(80));
+    }
+
+    #[test]
+    fn resync_items() {
+        let ms = |value| Time::from_duration(Duration::from_millis(value));
+        let mut items = vec![item(1, 10, 20, "a"), item(2, 30, 40, "b"), item(3, 110, 120, "c")];
+        // 10s -> 12s, 110s -> 122s: speed factor is 1.1 with 1s offset
+        resync(&mut items, time(10), time(12), time(110), time(122));
+        assert_eq!(items[0].start_time, time(12));
+        assert_eq!(items[0].end_time, time(23));
+        assert_eq!(items[1].start_time, time(34));
+        assert_eq!(items[1].end_time, time(45));
+        assert_eq!(items[2].start_time, ms(122_000));
+        assert_eq!(items[2].end_time, ms(133_000));
+
+        let mut items = vec![item(1, 1, 2, "a")];
+        resync(&mut items, time(0), time(0), time(3), time(1));
+        assert_eq!(items[0].start_time, ms(333));
+        assert_eq!(items[0].end_time, ms(667));
+
+        let mut items = vec![item(1, 1, 2, "a")];
+        resync(&mut items, time(5), time(3), time(5), time(3));
+        assert_eq!(items, vec![item(1, 0, 0, "a")]);
+    }
+
+    #[test]
+    fn shift_range_items() {
+        let items = (1..=6).map(|pos| item(pos, pos as u64 * 2, pos as u64 * 2 + 1, "a"));
+        let mut items = items.collect::<Vec<_>>();
+        let expected = items.clone();
+        // positions 3..=5
+        shift_range(&mut items, 2, 4, 1000);
+        assert_eq!(items[..2], expected[..2]);
+        assert_eq!(items[2], item(3, 7, 8, "a"));
+        assert_eq!(items[3], item(4, 9, 10, "a"));
+        assert_eq!(items[4], item(5, 11, 12, "a"));
+        assert_eq!(items[5], expected[5]);
+
+        shift_range(&mut items, 4, 100, -20000);
+        assert_eq!(items[4], item(5, 0, 0, "a"));
+        assert_eq!(items[5], item(6, 0, 0, "a"));
+        shift_range(&mut items, 10, 20, 1000);
+        shift_range(&mut items, 3, 2, 1000);
+        shift_range(&mut [], 0, 0, 1000);
+        assert_eq!(
+            items[..4],
+            [
+                expected[0].clone(),
+                expected[1].clone(),
+                item(3, 7, 8, "a"),
+                item(4, 9, 10, "a")
+            ]
+        );
+    }
+
+    #[test]
+    fn drop_zero_duration_items() {
+        let mut items = vec![item(1, 1, 2, "a"), item(2, 3, 3, "b"), item(3, 4, 5, "c")];
+        drop_zero_duration(&mut items);
+        assert_eq!(items, vec![item(1, 1, 2, "a"), item(2, 4, 5, "c")]);
+        drop_zero_duration(&mut items);
+        assert_eq!(items, vec![item(1, 1, 2, "a"), item(2, 4, 5, "c")]);
+    }
+
+    #[test]
+    fn rebase_to_zero_items() {
+        let mut items = vec![item(1, 5, 6, "a"), item(2, 8, 10, "b")];
+        rebase_to_zero(&mut items);
+        assert_eq!(items, vec![item(1, 0, 1, "a"), item(2, 3, 5, "b")]);
+        rebase_to_zero(&mut items);
+        assert_eq!(items, vec![item(1, 0, 1, "a"), item(2, 3, 5, "b")]);
+        rebase_to_zero(&mut []);
+    }
+
+    #[test]
+    fn insert_sorted_items() {
+        let mut items = vec![item(1, 1, 2, "a"), item(2, 5, 6, "c")];
+        insert_sorted(&mut items, item(10, 3, 4, "b"));
+        assert_eq!(items, vec![item(1, 1, 2, "a"), item(2, 3, 4, "b"), item(3, 5, 6, "c")]);
+        insert_sorted(&mut items, item(0, 0, 1, "first"));
+        insert_sorted(&mut items, item(0, 7, 8, "last"));
+        insert_sorted(&mut items, item(0, 3, 5, "b2"));
+        let texts = items.iter().map(|item| item.text.as_str()).collect::<Vec<_>>();
+        assert_eq!(texts, vec!["first", "a", "b", "b2", "c", "last"]);
+        assert_eq!(items[5].pos, 6);
+    }
+
+    #[test]
+    fn split_long() {
+        let mut items = vec![
+            item(1, 1, 2, "short"),
+            item(2, 10, 14, "This is the first sentence. And here\nis the second one"),
+            item(3, 20, 22, "fits"),
+        ];
+        split_long_cues(&mut items, 30);
+        assert_eq!(
+            items.iter().map(|item| item.text.as_str()).collect::<Vec<_>>(),
+            [
+                "short",
+                "This is the first sentence.",
+                "And here is the second one",
+                "fits"
+            ]
+        );
+        assert_eq!(items.iter().map(|item| item.pos).collect::<Vec<_>>(), [1, 2, 3, 4]);
+        // 27 and 26 characters share 4 seconds
+        assert_eq!(items[1].start_time, time(10));
+        assert_eq!(items[1].end_time, Time::from_duration(Duration::from_millis(12_037)));
+        assert_eq!(items[2].start_time, items[1].end_time);
+        assert_eq!(items[2].end_time, time(14));
+
+        let mut items = vec![item(1, 0, 3, "one two three four five six")];
+        split_long_cues(&mut items, 9);
+        assert_eq!(
+            items.iter().map(|item| item.text.as_str()).collect::<Vec<_>>(),
+            ["one two", "three", "four five", "six"]
+        );
+        assert_eq!(items[3].end_time, time(3));
+    }
+
+    #[test]
+    fn anchor_to_target() {
+        let mut items = vec![item(1, 1, 2, "a"), item(2, 5, 6, "b"), item(3, 8, 9, "c")];
+        anchor(&mut items, 1, time(10));
+        let starts = items.iter().map(|item| item.start_time).collect::<Vec<_>>();
+        assert_eq!(starts, [time(6), time(10), time(13)]);
+        assert!(items.iter().all(|item| item.duration() == Duration::from_secs(1)));
+
+        anchor(&mut items, 2, time(1));
+        let starts = items.iter().map(|item| item.start_time).collect::<Vec<_>>();
+        assert_eq!(starts, [time(0), time(0), time(1)]);
+        assert_eq!(items[1].end_time, time(0));
+
+        anchor(&mut items, 3, time(100));
+        assert_eq!(items[2].start_time, time(1));
+    }
+}
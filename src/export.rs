@@ -0,0 +1,15 @@
+#[cfg(feature = "csv")]
+pub use self::csv::to_csv;
+#[cfg(feature = "json")]
+pub use self::json::to_json;
+pub use self::lrc::to_lrc;
+#[cfg(feature = "ttml")]
+pub use self::ttml::to_ttml;
+
+#[cfg(feature = "csv")]
+mod csv;
+#[cfg(feature = "json")]
+mod json;
+mod lrc;
+#[cfg(feature = "ttml")]
+mod ttml;
@@ -0,0 +1,80 @@
+use crate::item::Item;
+use alloc::string::{String, ToString};
+
+/// Converts subtitles to CSV
+///
+/// Output starts with `index,start,end,text` header and follows RFC 4180:
+/// records are separated by CRLF and fields containing commas, quotes or line breaks are quoted.
+pub fn to_csv(items: &[Item]) -> String {
+    let mut result = String::from("index,start,end,text\r\n");
+    for item in items {
+        result.push_str(&item.pos.to_string());
+        for field in [
+            item.start_time.to_string(),
+            item.end_time.to_string(),
+            item.text.clone(),
+        ] {
+            result.push(',');
+            push_field(&mut result, &field);
+        }
+        result.push_str("\r\n");
+    }
+    result
+}
+
+fn push_field(out: &mut String, value: &str) {
+    if value.contains([',', '"', '\r', '\n']) {
+        out.push('"');
+        out.push_str(&value.replace('"', "\"\""));
+        out.push('"');
+    } else {
+        out.push_str(value);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::time::Time;
+
+    fn item(pos: usize, text: &str) -> Item {
+        Item {
+            pos,
+            start_time: Time {
+                hours: 0,
+                minutes: 0,
+                seconds: pos as u64,
+                milliseconds: 0,
+            },
+            end_time: Time {
+                hours: 0,
+                minutes: 0,
+                seconds: pos as u64,
+                milliseconds: 500,
+            },
+            text: String::from(text),
+            label: None,
+            coordinates: None,
+        }
+    }
+
+    #[test]
+    fn csv() {
+        let items = vec![
+            item(1, "Hello, world"),
+            item(2, "Say \"hi\""),
+            item(3, "first\nsecond"),
+            item(4, "plain"),
+        ];
+        assert_eq!(
+            to_csv(&items),
+            concat!(
+                "index,start,end,text\r\n",
+                "1,\"00:00:01,000\",\"00:00:01,500\",\"Hello, world\"\r\n",
+                "2,\"00:00:02,000\",\"00:00:02,500\",\"Say \"\"hi\"\"\"\r\n",
+                "3,\"00:00:03,000\",\"00:00:03,500\",\"first\nsecond\"\r\n",
+                "4,\"00:00:04,000\",\"00:00:04,500\",plain\r\n",
+            )
+        );
+    }
+}
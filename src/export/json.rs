@@ -0,0 +1,84 @@
+use crate::item::Item;
+use alloc::{format, string::String};
+
+/// Converts subtitles to a JSON array
+///
+/// Each subtitle is represented by an object with `index`, `start`, `end` and `text` fields,
+/// where `start` and `end` are formatted as `HH:MM:SS,mmm`.
+pub fn to_json(items: &[Item]) -> String {
+    let mut result = String::from("[");
+    for (idx, item) in items.iter().enumerate() {
+        if idx > 0 {
+            result.push(',');
+        }
+        result.push_str(&format!(
+            r#"{{"index":{},"start":"{}","end":"{}","text":"#,
+            item.pos, item.start_time, item.end_time
+        ));
+        push_string(&mut result, &item.text);
+        result.push('}');
+    }
+    result.push(']');
+    result
+}
+
+fn push_string(out: &mut String, value: &str) {
+    out.push('"');
+    for c in value.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c if u32::from(c) < 0x20 => out.push_str(&format!("\\u{:04x}", u32::from(c))),
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::time::Time;
+
+    fn time(seconds: u64, milliseconds: u64) -> Time {
+        Time {
+            hours: 0,
+            minutes: 0,
+            seconds,
+            milliseconds,
+        }
+    }
+
+    #[test]
+    fn json() {
+        let items = vec![
+            Item {
+                pos: 1,
+                start_time: time(1, 5),
+                end_time: time(2, 0),
+                text: String::from("Hello,\n\"world\""),
+                label: None,
+                coordinates: None,
+            },
+            Item {
+                pos: 2,
+                start_time: time(3, 500),
+                end_time: time(4, 250),
+                text: String::from("tab\there\\"),
+                label: None,
+                coordinates: None,
+            },
+        ];
+        assert_eq!(
+            to_json(&items),
+            concat!(
+                r#"[{"index":1,"start":"00:00:01,005","end":"00:00:02,000","text":"Hello,\n\"world\""},"#,
+                r#"{"index":2,"start":"00:00:03,500","end":"00:00:04,250","text":"tab\there\\"}]"#
+            )
+        );
+        assert_eq!(to_json(&[]), "[]");
+    }
+}
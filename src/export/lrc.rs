@@ -0,0 +1,96 @@
+use crate::item::Item;
+use alloc::{format, string::String};
+
+/// Converts subtitles to LRC (lyrics) format
+///
+/// LRC has no end times, so each subtitle becomes a single `[mm:ss.xx]text` line using its start time,
+/// where minutes include hours and `xx` are centiseconds (truncated).
+/// Lines of a multi-line subtitle are joined with spaces.
+pub fn to_lrc(items: &[Item]) -> String {
+    let mut result = String::new();
+    for item in items {
+        let milliseconds = item.start_time.total_milliseconds();
+        result.push_str(&format!(
+            "[{:02}:{:02}.{:02}]",
+            milliseconds / 60_000,
+            milliseconds / 1000 % 60,
+            milliseconds % 1000 / 10
+        ));
+        for (idx, line) in item.text.lines().enumerate() {
+            if idx > 0 {
+                result.push(' ');
+            }
+            result.push_str(line);
+        }
+        result.push('\n');
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::time::Time;
+
+    #[test]
+    fn lrc() {
+        let items = vec![
+            Item {
+                pos: 1,
+                start_time: Time {
+                    hours: 0,
+                    minutes: 1,
+                    seconds: 2,
+                    milliseconds: 345,
+                },
+                end_time: Time {
+                    hours: 0,
+                    minutes: 1,
+                    seconds: 4,
+                    milliseconds: 0,
+                },
+                text: String::from("Hello"),
+                label: None,
+                coordinates: None,
+            },
+            Item {
+                pos: 2,
+                start_time: Time {
+                    hours: 1,
+                    minutes: 0,
+                    seconds: 5,
+                    milliseconds: 70,
+                },
+                end_time: Time {
+                    hours: 1,
+                    minutes: 0,
+                    seconds: 6,
+                    milliseconds: 0,
+                },
+                text: String::from("first\nsecond"),
+                label: None,
+                coordinates: None,
+            },
+        ];
+        assert_eq!(to_lrc(&items), "[01:02.34]Hello\n[60:05.07]first second\n");
+    }
+
+    #[test]
+    fn lrc_unnormalized() {
+        let time = Time {
+            hours: 0,
+            minutes: 0,
+            seconds: 75,
+            milliseconds: 1500,
+        };
+        let items = vec![Item {
+            pos: 1,
+            start_time: time,
+            end_time: time,
+            text: String::from("Hello"),
+            label: None,
+            coordinates: None,
+        }];
+        assert_eq!(to_lrc(&items), "[01:16.50]Hello\n");
+    }
+}
@@ -0,0 +1,87 @@
+use crate::{item::Item, time::Time};
+use alloc::{format, string::String};
+
+/// Converts subtitles to TTML (Timed Text Markup Language) document
+///
+/// Each subtitle becomes a `<p>` element inside `<tt><body><div>`,
+/// times are formatted as `HH:MM:SS.mmm` and line breaks are converted to `<br/>`.
+pub fn to_ttml(items: &[Item]) -> String {
+    let mut result = String::from(concat!(
+        r#"<?xml version="1.0" encoding="UTF-8"?>"#,
+        "\n",
+        r#"<tt xmlns="http://www.w3.org/ns/ttml"><body><div>"#,
+        "\n"
+    ));
+    for item in items {
+        result.push_str(&format!(
+            r#"<p begin="{}" end="{}">"#,
+            format_time(&item.start_time),
+            format_time(&item.end_time)
+        ));
+        for (idx, line) in item.text.lines().enumerate() {
+            if idx > 0 {
+                result.push_str("<br/>");
+            }
+            push_escaped(&mut result, line);
+        }
+        result.push_str("</p>\n");
+    }
+    result.push_str("</div></body></tt>\n");
+    result
+}
+
+fn format_time(time: &Time) -> String {
+    format!(
+        "{:02}:{:02}:{:02}.{:03}",
+        time.hours, time.minutes, time.seconds, time.milliseconds
+    )
+}
+
+fn push_escaped(out: &mut String, value: &str) {
+    for c in value.chars() {
+        match c {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            '\'' => out.push_str("&apos;"),
+            c => out.push(c),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ttml() {
+        let items = vec![Item {
+            pos: 1,
+            start_time: Time {
+                hours: 1,
+                minutes: 2,
+                seconds: 3,
+                milliseconds: 4,
+            },
+            end_time: Time {
+                hours: 1,
+                minutes: 2,
+                seconds: 5,
+                milliseconds: 600,
+            },
+            text: String::from("Tom & \"Jerry\"\n<i>run</i>"),
+            label: None,
+            coordinates: None,
+        }];
+        let result = to_ttml(&items);
+        assert!(
+            result.starts_with("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<tt xmlns=\"http://www.w3.org/ns/ttml\">")
+        );
+        assert!(result.ends_with("</tt>\n"));
+        assert!(result.contains(concat!(
+            r#"<p begin="01:02:03.004" end="01:02:05.600">"#,
+            "Tom &amp; &quot;Jerry&quot;<br/>&lt;i&gt;run&lt;/i&gt;</p>"
+        )));
+    }
+}
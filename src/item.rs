@@ -1,10 +1,17 @@
-use crate::time::Time;
-use std::{error::Error, fmt};
+use crate::{
+    markup::{self, MarkupNode},
+    time::Time,
+};
+use alloc::{string::String, vec::Vec};
+use core::{error::Error, fmt, time::Duration};
 
 /// A subtitle item
-#[derive(Clone, Debug, PartialEq)]
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
 pub struct Item {
     /// A number indicating which subtitle it is in the sequence
+    ///
+    /// Original formatting is not preserved: surrounding whitespaces and leading zeros are dropped,
+    /// so `007` is parsed as `7` and written back as `7`.
     pub pos: usize,
     /// The time that the subtitle should appear
     pub start_time: Time,
@@ -12,15 +19,282 @@ pub struct Item {
     pub end_time: Time,
     /// The subtitle itself
     pub text: String,
+    /// An original non-numeric identifier of the subtitle (e.g. WebVTT cue identifier)
+    ///
+    /// Populated only when parser is configured to keep labels,
+    /// see [`ParserOptions::labels`](crate::ParserOptions::labels).
+    pub label: Option<String>,
+    /// A rectangle where the subtitle should be displayed
+    ///
+    /// Specified after the end time as `X1:100 X2:200 Y1:300 Y2:400`.
+    pub coordinates: Option<Coordinates>,
 }
 
+/// Subtitle position on the screen
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Coordinates {
+    /// Left border
+    pub x1: u32,
+    /// Right border
+    pub x2: u32,
+    /// Top border
+    pub y1: u32,
+    /// Bottom border
+    pub y2: u32,
+}
+
+impl fmt::Display for Coordinates {
+    fn fmt(&self, out: &mut fmt::Formatter) -> fmt::Result {
+        write!(out, "X1:{} X2:{} Y1:{} Y2:{}", self.x1, self.x2, self.y1, self.y2)
+    }
+}
+
+impl Item {
+    /// Returns how long the subtitle is displayed
+    ///
+    /// Zero when end time precedes start time.
+    pub fn duration(&self) -> Duration {
+        self.end_time
+            .into_duration()
+            .saturating_sub(self.start_time.into_duration())
+    }
+
+    /// Returns how long both subtitles are displayed simultaneously
+    ///
+    /// Returns `None` when subtitles do not overlap, including those where one ends exactly when another starts.
+    pub fn overlap(&self, other: &Item) -> Option<Duration> {
+        let start = self.start_time.into_duration().max(other.start_time.into_duration());
+        let end = self.end_time.into_duration().min(other.end_time.into_duration());
+        Some(end.saturating_sub(start)).filter(|overlap| !overlap.is_zero())
+    }
+
+    /// Compares subtitles ignoring their positions and labels
+    ///
+    /// Only start time, end time and text are compared.
+    pub fn eq_ignore_pos(&self, other: &Item) -> bool {
+        self.start_time == other.start_time && self.end_time == other.end_time && self.text == other.text
+    }
+
+    /// Returns text without markup tags like `<i>` or `<font color="red">`
+    ///
+    /// `<br>` tag is replaced with a line break, override blocks like `{\an8}` are removed,
+    /// an unclosed `<` or `{` is kept as is.
+    pub fn plain_text(&self) -> String {
+        let mut result = String::with_capacity(self.text.len());
+        let mut rest = self.text.as_str();
+        while let Some(start) = rest.find(['<', '{']) {
+            result.push_str(&rest[..start]);
+            let close = if rest[start..].starts_with('<') { '>' } else { '}' };
+            match rest[start..].find(close) {
+                Some(end) => {
+                    if close == '>' && markup::is_line_break(&rest[start + 1..start + end]) {
+                        result.push('\n');
+                    }
+                    rest = &rest[start + end + 1..];
+                }
+                None => {
+                    rest = &rest[start..];
+                    break;
+                }
+            }
+        }
+        result.push_str(rest);
+        result
+    }
+
+    /// Returns alignment from an `{\anN}` override block
+    ///
+    /// Alignment is a numpad position from 1 to 9, e.g. 8 means top center.
+    pub fn position_override(&self) -> Option<u8> {
+        let mut rest = self.text.as_str();
+        while let Some(start) = rest.find('{') {
+            let end = start + rest[start..].find('}')?;
+            for tag in rest[start + 1..end].split('\\').skip(1) {
+                if let Some(Ok(alignment @ 1..=9)) = tag.strip_prefix("an").map(|value| value.trim().parse::<u8>()) {
+                    return Some(alignment);
+                }
+            }
+            rest = &rest[end + 1..];
+        }
+        None
+    }
+
+    /// Returns text parsed into markup nodes
+    ///
+    /// Supported tags are `<i>`, `<b>`, `<u>`, `<font>` and `<br>`, other tags are dropped keeping their content.
+    pub fn markup(&self) -> Vec<MarkupNode> {
+        markup::parse(&self.text)
+    }
+
+    /// Whether text contains right-to-left characters or bidirectional control marks
+    ///
+    /// Hebrew, Arabic, Syriac, Thaana, NKo and related presentation forms are considered right-to-left.
+    pub fn contains_rtl(&self) -> bool {
+        self.text.chars().any(|c| {
+            matches!(c,
+                '\u{0590}'..='\u{08FF}'
+                | '\u{FB1D}'..='\u{FDFF}'
+                | '\u{FE70}'..='\u{FEFC}'
+                | '\u{200F}'
+                | '\u{202B}'
+                | '\u{202E}'
+            )
+        })
+    }
+
+    /// Returns text without bidirectional control marks
+    ///
+    /// Removed marks are LRM and RLM (U+200E, U+200F) and embeddings and overrides (U+202A–U+202E).
+    pub fn strip_bidi_controls(&self) -> String {
+        self.text
+            .chars()
+            .filter(|c| !matches!(c, '\u{200E}' | '\u{200F}' | '\u{202A}'..='\u{202E}'))
+            .collect()
+    }
+
+    /// Returns text on a single line
+    ///
+    /// Any run of whitespaces including line breaks is replaced with a single space, leading and trailing ones are removed.
+    pub fn single_line(&self) -> String {
+        self.text.split_whitespace().collect::<Vec<_>>().join(" ")
+    }
+
+    /// Returns sound descriptions enclosed in square brackets or parentheses, e.g. `[door creaks]` or `(sighs)`
+    ///
+    /// Descriptions are taken from [`Item::plain_text`] and returned without brackets,
+    /// nested and unclosed brackets are not supported.
+    pub fn sound_effects(&self) -> Vec<String> {
+        let text = self.plain_text();
+        let mut result = Vec::new();
+        let mut rest = text.as_str();
+        while let Some(start) = rest.find(['[', '(']) {
+            let close = if rest[start..].starts_with('[') { ']' } else { ')' };
+            let Some(end) = rest[start..].find(close) else {
+                break;
+            };
+            let effect = rest[start + 1..start + end].trim();
+            if !effect.is_empty() {
+                result.push(String::from(effect));
+            }
+            rest = &rest[start + end + 1..];
+        }
+        result
+    }
+
+    /// Returns a speaker name prefixed to the first line of text
+    ///
+    /// A prefix is the part of the first line before a colon, optionally preceded by a dialogue dash.
+    /// It is considered to be a speaker name when it is either written in capitals, e.g. `JOHN: Hi`,
+    /// or follows a dialogue dash and consists of at most two capitalized words, e.g. `- Mary Jane: Hi`.
+    /// Without a dash mixed-case prefixes are ignored, so `Note: this is fine` has no speaker.
+    pub fn speaker(&self) -> Option<&str> {
+        let line = self.text.lines().next()?.trim_start();
+        let (has_dash, line) = match line.strip_prefix('-') {
+            Some(line) => (true, line),
+            None => (false, line),
+        };
+        let (name, _) = line.split_once(':')?;
+        let name = name.trim();
+        if !name.starts_with(|c: char| c.is_alphabetic()) {
+            return None;
+        }
+        let is_name_char = |c: char| c.is_alphabetic() || matches!(c, ' ' | '.' | '\'' | '-');
+        if !name.chars().all(is_name_char) {
+            return None;
+        }
+        let is_capitals = !name.chars().any(char::is_lowercase);
+        let is_capitalized = has_dash
+            && name.split(' ').count() <= 2
+            && name.split(' ').all(|word| word.starts_with(|c: char| c.is_uppercase()));
+        if is_capitals || is_capitalized {
+            Some(name)
+        } else {
+            None
+        }
+    }
+
+    /// Returns number of characters in text
+    ///
+    /// Line breaks are never counted, markup tags are not counted when `strip_markup` is `true`
+    /// and other whitespaces are counted only when `count_spaces` is `true`.
+    pub fn char_count(&self, strip_markup: bool, count_spaces: bool) -> usize {
+        let count = |text: &str| {
+            text.chars()
+                .filter(|c| *c != '\n' && *c != '\r' && (count_spaces || !c.is_whitespace()))
+                .count()
+        };
+        if strip_markup {
+            count(&self.plain_text())
+        } else {
+            count(&self.text)
+        }
+    }
+
+    /// Whether text looks like it was decoded using a wrong encoding
+    ///
+    /// This is a heuristic, text is considered broken when either more than 10% of its non-whitespace characters
+    /// are suspicious or it has at least 8 letters and more than a half of them are from Latin-1 supplement
+    /// (U+00C0–U+00FF), which is typical for Windows-1251 text read as Latin-1.
+    /// The letter threshold keeps short accented text like `À été` from being reported.
+    /// Suspicious characters are replacement characters (U+FFFD) left by lossy decoding, C1 control characters
+    /// and non-ASCII characters following `Â`, `Ã`, `Ð` or `Ñ`, which is typical for UTF-8 text read as Windows-1252.
+    pub fn looks_like_mojibake(&self) -> bool {
+        let mut chars = 0;
+        let mut letters = 0;
+        let mut latin1 = 0;
+        let mut suspicious = 0;
+        let mut prev = ' ';
+        for c in self.text.chars().filter(|c| !c.is_whitespace()) {
+            chars += 1;
+            if c == '\u{FFFD}'
+                || (c.is_control() && !c.is_ascii())
+                || (!c.is_ascii() && matches!(prev, 'Â' | 'Ã' | 'Ð' | 'Ñ'))
+            {
+                suspicious += 1;
+            }
+            if c.is_alphabetic() {
+                letters += 1;
+                if matches!(c, '\u{C0}'..='\u{FF}') {
+                    latin1 += 1;
+                }
+            }
+            prev = c;
+        }
+        suspicious * 10 > chars || (letters >= 8 && latin1 * 2 > letters)
+    }
+
+    /// Returns reading speed in characters per second
+    ///
+    /// Characters of [`Item::plain_text`] are counted, line breaks are not.
+    /// Returns `f64::INFINITY` for a subtitle with zero duration.
+    pub fn reading_speed(&self) -> f64 {
+        let chars = self.char_count(true, true);
+        let duration = self.duration();
+        if duration.is_zero() {
+            f64::INFINITY
+        } else {
+            chars as f64 / duration.as_secs_f64()
+        }
+    }
+}
+
+/// A subtitle item
+///
+/// This is the name used before 0.2.0, `from_*` functions return [`Item`]s which are the same type.
+#[deprecated(since = "0.3.0", note = "use `Item` instead")]
+pub type Subtitle = Item;
+
 impl fmt::Display for Item {
     fn fmt(&self, out: &mut fmt::Formatter) -> fmt::Result {
-        write!(
-            out,
-            "{}\n{}-->{}\n{}",
-            self.pos, self.start_time, self.end_time, self.text
-        )
+        match &self.label {
+            Some(label) => write!(out, "{label}")?,
+            None => write!(out, "{}", self.pos)?,
+        }
+        write!(out, "\n{}-->{}", self.start_time, self.end_time)?;
+        if let Some(coordinates) = &self.coordinates {
+            write!(out, " {coordinates}")?;
+        }
+        write!(out, "\n{}", self.text)
     }
 }
 
@@ -30,6 +304,8 @@ pub(super) struct ItemFactory {
     start_time: Option<Time>,
     end_time: Option<Time>,
     text: Option<String>,
+    label: Option<String>,
+    coordinates: Option<Coordinates>,
 }
 
 impl ItemFactory {
@@ -37,6 +313,14 @@ impl ItemFactory {
         self.pos = Some(pos);
     }
 
+    pub(super) fn set_label(&mut self, label: String) {
+        self.label = Some(label);
+    }
+
+    pub(super) fn set_coordinates(&mut self, coordinates: Coordinates) {
+        self.coordinates = Some(coordinates);
+    }
+
     pub(super) fn set_start_time(&mut self, start_time: Time) {
         self.start_time = Some(start_time);
     }
@@ -45,11 +329,11 @@ impl ItemFactory {
         self.end_time = Some(end_time);
     }
 
-    pub(super) fn append_text<P: AsRef<str>>(&mut self, part: P) {
+    pub(super) fn append_text<P: AsRef<str>>(&mut self, part: P, separator: &str) {
         let part = part.as_ref();
         match self.text.as_mut() {
             Some(text) => {
-                text.push('\n');
+                text.push_str(separator);
                 text.push_str(part);
             }
             None => {
@@ -58,6 +342,10 @@ impl ItemFactory {
         }
     }
 
+    pub(super) fn ensure_text(&mut self) {
+        self.text.get_or_insert_with(String::new);
+    }
+
     pub(super) fn maybe_ready(&self) -> bool {
         self.pos.is_some()
     }
@@ -68,6 +356,8 @@ impl ItemFactory {
             start_time: self.start_time.take().ok_or(ItemFactoryError::NoStartTime)?,
             end_time: self.end_time.take().ok_or(ItemFactoryError::NoEndTime)?,
             text: self.text.take().ok_or(ItemFactoryError::NoText)?,
+            label: self.label.take(),
+            coordinates: self.coordinates.take(),
         })
     }
 }
@@ -120,7 +410,238 @@ mod tests {
                 milliseconds: 300,
             },
             text: String::from("test"),
+            label: None,
+            coordinates: None,
         };
         assert_eq!(item.to_string(), "1\n00:00:05,200-->00:00:06,300\ntest");
     }
+
+    #[test]
+    fn display_label() {
+        let item = Item {
+            pos: 1,
+            start_time: Time {
+                hours: 0,
+                minutes: 0,
+                seconds: 5,
+                milliseconds: 200,
+            },
+            end_time: Time {
+                hours: 0,
+                minutes: 0,
+                seconds: 6,
+                milliseconds: 300,
+            },
+            text: String::from("test"),
+            label: Some(String::from("intro")),
+            coordinates: None,
+        };
+        assert_eq!(item.to_string(), "intro\n00:00:05,200-->00:00:06,300\ntest");
+    }
+
+    #[test]
+    fn hash() {
+        let item = Item {
+            pos: 1,
+            start_time: Time {
+                hours: 0,
+                minutes: 0,
+                seconds: 5,
+                milliseconds: 200,
+            },
+            end_time: Time {
+                hours: 0,
+                minutes: 0,
+                seconds: 6,
+                milliseconds: 300,
+            },
+            text: String::from("test"),
+            label: None,
+            coordinates: None,
+        };
+        let set = std::collections::HashSet::from([item.clone(), item]);
+        assert_eq!(set.len(), 1);
+    }
+
+    fn item(start: u64, end: u64, text: &str) -> Item {
+        let time = |milliseconds: u64| Time {
+            hours: 0,
+            minutes: 0,
+            seconds: milliseconds / 1000,
+            milliseconds: milliseconds % 1000,
+        };
+        Item {
+            pos: 1,
+            start_time: time(start),
+            end_time: time(end),
+            text: String::from(text),
+            label: None,
+            coordinates: None,
+        }
+    }
+
+    #[test]
+    fn duration() {
+        assert_eq!(item(1000, 2500, "a").duration(), Duration::from_millis(1500));
+        assert_eq!(item(2000, 1000, "a").duration(), Duration::ZERO);
+    }
+
+    #[test]
+    fn plain_text() {
+        assert_eq!(item(0, 1, "plain").plain_text(), "plain");
+        assert_eq!(
+            item(0, 1, "<i>Hello</i>\n<font color=\"red\">world</font>").plain_text(),
+            "Hello\nworld"
+        );
+        assert_eq!(item(0, 1, "a < b").plain_text(), "a < b");
+    }
+
+    #[test]
+    fn reading_speed() {
+        // 30 characters in 2 seconds
+        let comfortable = item(1000, 3000, "<i>Hello there,</i>\nhow are you today?");
+        assert_eq!(comfortable.plain_text().chars().count(), 31);
+        assert_eq!(comfortable.reading_speed(), 15.0);
+        let fast = item(1000, 1100, "Way too much text for such a short time");
+        assert!(fast.reading_speed() > 100.0);
+        assert_eq!(item(1000, 1000, "text").reading_speed(), f64::INFINITY);
+    }
+
+    #[test]
+    fn markup() {
+        assert_eq!(
+            item(0, 1, "<i><b>x</b></i>\ny").markup(),
+            vec![
+                MarkupNode::Italic(vec![MarkupNode::Bold(vec![MarkupNode::Text(String::from("x"))])]),
+                MarkupNode::LineBreak,
+                MarkupNode::Text(String::from("y")),
+            ]
+        );
+    }
+
+    #[test]
+    fn eq_ignore_pos() {
+        let first = item(1000, 2000, "text");
+        let mut second = first.clone();
+        second.pos = 5;
+        second.label = Some(String::from("label"));
+        assert_ne!(first, second);
+        assert!(first.eq_ignore_pos(&second));
+        second.text.push('!');
+        assert!(!first.eq_ignore_pos(&second));
+        assert!(!first.eq_ignore_pos(&item(1000, 2001, "text")));
+    }
+
+    #[test]
+    fn plain_text_br() {
+        for tag in ["<br>", "<br/>", "<br />", "<BR>"] {
+            assert_eq!(
+                item(0, 1, &format!("<i>first</i>{tag}second")).plain_text(),
+                "first\nsecond"
+            );
+        }
+    }
+
+    #[test]
+    fn bidi() {
+        let rtl = item(0, 1, "\u{200F}שלום!\n\u{202B}مرحبا\u{202C}");
+        assert!(rtl.contains_rtl());
+        assert_eq!(rtl.strip_bidi_controls(), "שלום!\nمرحبا");
+        assert!(item(0, 1, "\u{200F}?").contains_rtl());
+
+        let ltr = item(0, 1, "Hello\n\u{200E}world");
+        assert!(!ltr.contains_rtl());
+        assert_eq!(ltr.strip_bidi_controls(), "Hello\nworld");
+        assert_eq!(item(0, 1, "plain").strip_bidi_controls(), "plain");
+    }
+
+    #[test]
+    fn char_count() {
+        let item = item(0, 1000, "<i>Hello there,</i>\nhow  are you?");
+        assert_eq!(item.char_count(true, true), 25);
+        assert_eq!(item.char_count(true, false), 21);
+        assert_eq!(item.char_count(false, true), 32);
+        assert_eq!(item.char_count(false, false), 28);
+        assert_eq!(item.char_count(true, true), item.reading_speed() as usize);
+    }
+
+    #[test]
+    fn single_line() {
+        assert_eq!(
+            item(0, 1000, "First line\r\nsecond line").single_line(),
+            "First line second line"
+        );
+        assert_eq!(item(0, 1000, "  a\t\tb   c \n").single_line(), "a b c");
+    }
+
+    #[test]
+    fn overlap() {
+        let first = item(1000, 3000, "first");
+        let partial = item(2500, 4000, "partial");
+        assert_eq!(first.overlap(&partial), Some(Duration::from_millis(500)));
+        assert_eq!(partial.overlap(&first), Some(Duration::from_millis(500)));
+        let inner = item(1500, 2000, "inner");
+        assert_eq!(first.overlap(&inner), Some(Duration::from_millis(500)));
+        assert_eq!(first.overlap(&item(3000, 4000, "adjacent")), None);
+        assert_eq!(first.overlap(&item(5000, 6000, "later")), None);
+    }
+
+    #[test]
+    fn speaker() {
+        assert_eq!(item(0, 1000, "JOHN: hi").speaker(), Some("JOHN"));
+        assert_eq!(item(0, 1000, "- MARY: there\n- JOHN: hi").speaker(), Some("MARY"));
+        assert_eq!(item(0, 1000, "- Mary Jane: hello").speaker(), Some("Mary Jane"));
+        assert_eq!(item(0, 1000, "Mary Jane: hello").speaker(), None);
+        assert_eq!(item(0, 1000, "Note: this is fine").speaker(), None);
+        assert_eq!(item(0, 1000, "Warning: hot").speaker(), None);
+        assert_eq!(item(0, 1000, "Step 1: open").speaker(), None);
+        assert_eq!(item(0, 1000, "DR. O'NEIL: hello").speaker(), Some("DR. O'NEIL"));
+        assert_eq!(item(0, 1000, "No speaker here").speaker(), None);
+        assert_eq!(item(0, 1000, "Meet me at 10:30").speaker(), None);
+        assert_eq!(item(0, 1000, "The thing is: no").speaker(), None);
+        assert_eq!(item(0, 1000, "").speaker(), None);
+    }
+
+    #[test]
+    fn override_tags() {
+        let top = item(0, 1, "{\\an8}<i>Top</i> text");
+        assert_eq!(top.plain_text(), "Top text");
+        assert_eq!(top.position_override(), Some(8));
+        assert_eq!(item(0, 1, "{\\b1\\an2}x").position_override(), Some(2));
+        assert_eq!(item(0, 1, "no override").position_override(), None);
+        assert_eq!(item(0, 1, "{\\an0} {\\i1}").position_override(), None);
+        assert_eq!(item(0, 1, "a { b").plain_text(), "a { b");
+    }
+
+    #[test]
+    fn sound_effects() {
+        assert_eq!(
+            item(0, 1, "[door creaks]\nWho's there? <i>(sighs)</i>").sound_effects(),
+            ["door creaks", "sighs"]
+        );
+        assert_eq!(item(0, 1, "[ MUSIC ] []").sound_effects(), ["MUSIC"]);
+        assert!(item(0, 1, "No effects (unclosed").sound_effects().is_empty());
+    }
+
+    #[test]
+    fn mojibake() {
+        assert!(!item(0, 1, "Привет, мир!\nКак дела?").looks_like_mojibake());
+        assert!(!item(0, 1, "Où est la bibliothèque? Ça va.").looks_like_mojibake());
+        assert!(!item(0, 1, "Plain text with a single broken \u{FFFD} character").looks_like_mojibake());
+        assert!(!item(0, 1, "").looks_like_mojibake());
+        for text in ["À été", "Où ça?", "Ça", "¿Qué?", "Sí, señor.", "¡Ñu!"] {
+            assert!(!item(0, 1, text).looks_like_mojibake(), "{text}");
+        }
+        // Windows-1251 read as Latin-1
+        assert!(item(0, 1, "Ïðèâåò, ìèð!").looks_like_mojibake());
+        // UTF-8 read as Windows-1252
+        assert!(item(0, 1, "ÐŸÑ€Ð¸Ð²ÐµÑ‚, Ð¼Ð¸Ñ€!").looks_like_mojibake());
+        // Windows-1251 decoded as lossy UTF-8
+        assert!(item(
+            0,
+            1,
+            "\u{FFFD}\u{FFFD}\u{FFFD}\u{FFFD}\u{FFFD}\u{FFFD}, \u{FFFD}\u{FFFD}\u{FFFD}!"
+        )
+        .looks_like_mojibake());
+    }
 }
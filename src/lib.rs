@@ -16,17 +16,76 @@
 //! println!("{:?}", items[0]);
 //! ```
 //!
+//! ## Reading lazily
+//!
+//! ```
+//! for item in srtparse::parse_iter(std::io::Cursor::new("1\n00:00:01,100 --> 00:00:02,120\nHello!")) {
+//!     println!("{:?}", item.unwrap());
+//! }
+//! ```
+//!
 //! [1]: https://matroska.org/technical/specs/subtitles/srt.html
+#![cfg_attr(not(any(feature = "std", test)), no_std)]
 #![warn(missing_docs)]
 
+extern crate alloc;
+
+#[allow(deprecated)]
+pub use self::item::Subtitle;
+
+#[cfg(feature = "csv")]
+pub use self::export::to_csv;
+#[cfg(feature = "json")]
+pub use self::export::to_json;
+#[cfg(feature = "ttml")]
+pub use self::export::to_ttml;
+#[cfg(feature = "tokio")]
+pub use self::reader::{from_async_reader, from_async_reader_with};
+#[cfg(feature = "gzip")]
+pub use self::reader::{from_gz_file, from_gz_file_with};
+#[cfg(feature = "std")]
+pub use self::{
+    parser::Parser,
+    reader::{
+        collect_all, from_dir, from_dir_recursive, from_file, from_file_with, from_read, from_reader,
+        from_reader_limited, from_reader_with, from_str, from_str_with, iter_str, nth_item, parse_iter,
+        parse_with_spans, parse_with_tail, try_fold_items, FileResult, ReaderError,
+    },
+    writer::{to_writer, to_writer_with},
+};
+
 pub use self::{
-    item::{Item, ItemFactoryError},
-    parser::ParseError,
-    reader::{from_file, from_reader, from_str, ReaderError},
-    time::{ParseTimeError, Time},
+    cue::Cue,
+    edit::{
+        anchor, clamp_to, coalesce_identical, dedup, drop_zero_duration, enforce_min_duration, fill_small_gaps,
+        insert_sorted, rebase_to_zero, reflow, remove_range, renumber, resync, shift, shift_range, split_at_time,
+        split_long_cues,
+    },
+    export::to_lrc,
+    item::{Coordinates, Item, ItemFactoryError},
+    markup::{normalize_color, MarkupNode},
+    parser::{parse_bytes_lossy, Diagnostic, LineJoin, ParseError, ParserBuilder, ParserOptions, PushParser},
+    repair::normalize_separators,
+    search::{active_at, search},
+    stats::{gap_histogram, gaps, summary, windows_pairs, GapIter, Summary},
+    subtitles::Subtitles,
+    time::{ParseTimeError, Time, TimePrecise},
+    validate::{find_too_long, find_too_many_lines, find_zero_duration, validate, ValidationIssue},
+    writer::{to_string, to_string_with, LineEnding, SrtDisplay},
 };
 
+mod cue;
+mod edit;
+mod export;
 mod item;
+mod markup;
 mod parser;
+#[cfg(feature = "std")]
 mod reader;
+mod repair;
+mod search;
+mod stats;
+mod subtitles;
 mod time;
+mod validate;
+mod writer;
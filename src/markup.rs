@@ -0,0 +1,315 @@
+use alloc::{format, string::String, vec::Vec};
+
+/// A node of subtitle text markup
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum MarkupNode {
+    /// Plain text
+    Text(String),
+    /// `<i>` tag
+    Italic(Vec<MarkupNode>),
+    /// `<b>` tag
+    Bold(Vec<MarkupNode>),
+    /// `<u>` tag
+    Underline(Vec<MarkupNode>),
+    /// `<font>` tag
+    Font {
+        /// Value of `color` attribute, normalized with [`normalize_color`] when possible
+        color: Option<String>,
+        /// Nested nodes
+        children: Vec<MarkupNode>,
+    },
+    /// A line break or `<br>` tag
+    LineBreak,
+}
+
+enum Tag {
+    Italic,
+    Bold,
+    Underline,
+    Font(Option<String>),
+}
+
+impl Tag {
+    fn parse(name: &str, attributes: &str) -> Option<Self> {
+        Some(if name.eq_ignore_ascii_case("i") {
+            Tag::Italic
+        } else if name.eq_ignore_ascii_case("b") {
+            Tag::Bold
+        } else if name.eq_ignore_ascii_case("u") {
+            Tag::Underline
+        } else if name.eq_ignore_ascii_case("font") {
+            Tag::Font(attribute(attributes, "color").map(|color| normalize_color(&color).unwrap_or(color)))
+        } else {
+            return None;
+        })
+    }
+
+    fn is_closed_by(&self, name: &str) -> bool {
+        match self {
+            Tag::Italic => name.eq_ignore_ascii_case("i"),
+            Tag::Bold => name.eq_ignore_ascii_case("b"),
+            Tag::Underline => name.eq_ignore_ascii_case("u"),
+            Tag::Font(_) => name.eq_ignore_ascii_case("font"),
+        }
+    }
+
+    fn into_node(self, children: Vec<MarkupNode>) -> MarkupNode {
+        match self {
+            Tag::Italic => MarkupNode::Italic(children),
+            Tag::Bold => MarkupNode::Bold(children),
+            Tag::Underline => MarkupNode::Underline(children),
+            Tag::Font(color) => MarkupNode::Font { color, children },
+        }
+    }
+}
+
+/// Parses subtitle text into markup nodes
+///
+/// Unknown tags are dropped keeping their content, unclosed tags are closed at the end of text
+/// and closing tags without a matching opening one are ignored.
+pub(crate) fn parse(text: &str) -> Vec<MarkupNode> {
+    let mut stack: Vec<(Tag, Vec<MarkupNode>)> = Vec::new();
+    let mut root = Vec::new();
+    let mut rest = text;
+    while !rest.is_empty() {
+        let (chunk, tag) = match rest.find('<') {
+            Some(start) => match rest[start..].find('>') {
+                Some(end) => {
+                    let tag = &rest[start + 1..start + end];
+                    let chunk = &rest[..start];
+                    rest = &rest[start + end + 1..];
+                    (chunk, Some(tag))
+                }
+                None => (core::mem::take(&mut rest), None),
+            },
+            None => (core::mem::take(&mut rest), None),
+        };
+        let children = stack.last_mut().map_or(&mut root, |(_, children)| children);
+        push_text(children, chunk);
+        let Some(tag) = tag.map(str::trim) else {
+            continue;
+        };
+        if let Some(name) = tag.strip_prefix('/') {
+            let name = name.trim();
+            if let Some(idx) = stack.iter().rposition(|(tag, _)| tag.is_closed_by(name)) {
+                while stack.len() > idx {
+                    close(&mut stack, &mut root);
+                }
+            }
+        } else if is_line_break(tag) {
+            stack
+                .last_mut()
+                .map_or(&mut root, |(_, children)| children)
+                .push(MarkupNode::LineBreak);
+        } else {
+            let tag = tag.trim_end_matches('/');
+            let (name, attributes) = tag.split_once(char::is_whitespace).unwrap_or((tag, ""));
+            if let Some(tag) = Tag::parse(name, attributes) {
+                stack.push((tag, Vec::new()));
+            }
+        }
+    }
+    while !stack.is_empty() {
+        close(&mut stack, &mut root);
+    }
+    root
+}
+
+/// Named HTML colors with their hex values
+const COLORS: [(&str, &str); 16] = [
+    ("black", "#000000"),
+    ("silver", "#c0c0c0"),
+    ("gray", "#808080"),
+    ("white", "#ffffff"),
+    ("maroon", "#800000"),
+    ("red", "#ff0000"),
+    ("purple", "#800080"),
+    ("fuchsia", "#ff00ff"),
+    ("green", "#008000"),
+    ("lime", "#00ff00"),
+    ("olive", "#808000"),
+    ("yellow", "#ffff00"),
+    ("navy", "#000080"),
+    ("blue", "#0000ff"),
+    ("teal", "#008080"),
+    ("aqua", "#00ffff"),
+];
+
+/// Converts a color to a canonical lowercase `#rrggbb` form
+///
+/// Accepts one of 16 basic HTML color names and `#rgb` or `#rrggbb` hex values,
+/// returns `None` for anything else.
+///
+/// ```
+/// assert_eq!(srtparse::normalize_color("Red").as_deref(), Some("#ff0000"));
+/// assert_eq!(srtparse::normalize_color("#FF0000").as_deref(), Some("#ff0000"));
+/// ```
+pub fn normalize_color(color: &str) -> Option<String> {
+    let color = color.trim();
+    if let Some(hex) = color.strip_prefix('#') {
+        if !hex.bytes().all(|b| b.is_ascii_hexdigit()) {
+            return None;
+        }
+        let hex = hex.to_ascii_lowercase();
+        return match hex.len() {
+            3 => Some(hex.chars().fold(String::from("#"), |mut acc, c| {
+                acc.push(c);
+                acc.push(c);
+                acc
+            })),
+            6 => Some(format!("#{hex}")),
+            _ => None,
+        };
+    }
+    COLORS
+        .iter()
+        .find(|(name, _)| name.eq_ignore_ascii_case(color))
+        .map(|(_, hex)| String::from(*hex))
+}
+
+/// Whether a tag content (without angle brackets) is `br`, `br/` or `br /`
+pub(crate) fn is_line_break(tag: &str) -> bool {
+    tag.trim().trim_end_matches('/').trim_end().eq_ignore_ascii_case("br")
+}
+
+fn close(stack: &mut Vec<(Tag, Vec<MarkupNode>)>, root: &mut Vec<MarkupNode>) {
+    if let Some((tag, children)) = stack.pop() {
+        let node = tag.into_node(children);
+        stack.last_mut().map_or(root, |(_, children)| children).push(node);
+    }
+}
+
+fn push_text(nodes: &mut Vec<MarkupNode>, text: &str) {
+    for (idx, line) in text.split('\n').enumerate() {
+        if idx > 0 {
+            nodes.push(MarkupNode::LineBreak);
+        }
+        if line.is_empty() {
+            continue;
+        }
+        match nodes.last_mut() {
+            Some(MarkupNode::Text(last)) => last.push_str(line),
+            _ => nodes.push(MarkupNode::Text(String::from(line))),
+        }
+    }
+}
+
+/// Returns value of an attribute from `name="value"`, `name='value'` or `name=value` form
+fn attribute(attributes: &str, name: &str) -> Option<String> {
+    let mut rest = attributes.trim();
+    while !rest.is_empty() {
+        let (key, tail) = rest.split_once('=')?;
+        let tail = tail.trim_start();
+        let (value, tail) = match tail.chars().next() {
+            Some(quote @ ('"' | '\'')) => {
+                let tail = &tail[1..];
+                tail.split_once(quote).unwrap_or((tail, ""))
+            }
+            _ => tail.split_once(char::is_whitespace).unwrap_or((tail, "")),
+        };
+        if key.trim().eq_ignore_ascii_case(name) {
+            return Some(String::from(value));
+        }
+        rest = tail.trim_start();
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use MarkupNode::*;
+
+    fn text(value: &str) -> MarkupNode {
+        Text(String::from(value))
+    }
+
+    #[test]
+    fn nested() {
+        assert_eq!(parse("<i><b>x</b></i>"), vec![Italic(vec![Bold(vec![text("x")])])]);
+        assert_eq!(
+            parse("a <I>b <u>c</u></I> d"),
+            vec![
+                text("a "),
+                Italic(vec![text("b "), Underline(vec![text("c")])]),
+                text(" d")
+            ]
+        );
+    }
+
+    #[test]
+    fn line_break() {
+        assert_eq!(parse("\n"), vec![LineBreak]);
+        assert_eq!(
+            parse("first\n<i>second</i>"),
+            vec![text("first"), LineBreak, Italic(vec![text("second")])]
+        );
+    }
+
+    #[test]
+    fn br_tag() {
+        for tag in ["<br>", "<br/>", "<br />", "<BR>"] {
+            assert_eq!(parse(&format!("a{tag}b")), vec![text("a"), LineBreak, text("b")]);
+        }
+        assert_eq!(
+            parse("<i>a<br>b</i>"),
+            vec![Italic(vec![text("a"), LineBreak, text("b")])]
+        );
+    }
+
+    #[test]
+    fn font() {
+        assert_eq!(
+            parse("<font color=\"#ff0000\" face='Arial'>red</font>"),
+            vec![Font {
+                color: Some(String::from("#ff0000")),
+                children: vec![text("red")]
+            }]
+        );
+        assert_eq!(
+            parse("<font face=Arial color=red>red</font>"),
+            vec![Font {
+                color: Some(String::from("#ff0000")),
+                children: vec![text("red")]
+            }]
+        );
+        assert_eq!(
+            parse("<font size=2>x</font>"),
+            vec![Font {
+                color: None,
+                children: vec![text("x")]
+            }]
+        );
+    }
+
+    #[test]
+    fn malformed() {
+        assert_eq!(parse("<i>unclosed"), vec![Italic(vec![text("unclosed")])]);
+        assert_eq!(parse("stray</b> tag"), vec![text("stray tag")]);
+        assert_eq!(
+            parse("<i><b>x</i>y"),
+            vec![Italic(vec![Bold(vec![text("x")])]), text("y")]
+        );
+        assert_eq!(parse("<span>x</span>"), vec![text("x")]);
+        assert_eq!(parse("a < b"), vec![text("a < b")]);
+        assert_eq!(parse(""), vec![]);
+    }
+
+    #[test]
+    fn color() {
+        assert_eq!(normalize_color("red").as_deref(), Some("#ff0000"));
+        assert_eq!(normalize_color("AQUA").as_deref(), Some("#00ffff"));
+        assert_eq!(normalize_color("#abc").as_deref(), Some("#aabbcc"));
+        assert_eq!(normalize_color("#AABBCC").as_deref(), Some("#aabbcc"));
+        assert_eq!(normalize_color("orange"), None);
+        assert_eq!(normalize_color("#abcd"), None);
+        assert_eq!(normalize_color("#ggg"), None);
+        assert_eq!(
+            parse("<font color=orange>x</font>"),
+            vec![Font {
+                color: Some(String::from("orange")),
+                children: vec![text("x")]
+            }]
+        );
+    }
+}
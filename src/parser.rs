@@ -1,117 +1,720 @@
 use crate::{
-    item::{Item, ItemFactory, ItemFactoryError},
-    time::ParseTimeError,
+    item::{Coordinates, Item, ItemFactory, ItemFactoryError},
+    time::{ParseTimeError, Time},
 };
+use alloc::{collections::BTreeSet, format, string::String, vec::Vec};
+use core::{error::Error, fmt, num::ParseIntError};
+#[cfg(feature = "std")]
 use std::{
-    error::Error,
-    fmt,
     io::{BufRead, Error as IoError, Lines},
-    num::ParseIntError,
+    ops::RangeInclusive,
 };
 
-const UTF8_BOM: &str = "\u{feff}";
+pub(crate) const UTF8_BOM: &str = "\u{feff}";
 const TIME_DELIMITER: &str = "-->";
+const LENIENT_TIME_DELIMITER: &str = "→";
+
+/// Subtitles parser options
+#[derive(Clone, Debug, Default)]
+pub struct ParserOptions {
+    /// Keep a non-numeric position as item label instead of failing with [`ParseError::BadPosition`]
+    ///
+    /// Position of such item is assigned automatically and follows the previous one.
+    pub labels: bool,
+    /// Produce an item with empty text instead of failing with [`ItemFactoryError::NoText`]
+    pub allow_empty_text: bool,
+    /// Assign positions automatically when a subtitle starts with a time line instead of a position
+    pub auto_index: bool,
+    /// Collect non-fatal [`Diagnostic`]s, see `Parser::diagnostics` and [`PushParser::diagnostics`]
+    pub diagnostics: bool,
+    /// Fail with [`ParseError::ReversedTimes`] when a subtitle ends before it starts
+    /// and with [`ParseError::NonMonotonic`] when a subtitle starts before the previous one
+    pub strict: bool,
+    /// Keep leading and trailing whitespaces of text lines
+    ///
+    /// Only line terminators are stripped, a line consisting of whitespaces still ends a subtitle.
+    pub preserve_text_whitespace: bool,
+    /// A separator inserted between text lines of a subtitle
+    pub line_join: LineJoin,
+    /// Accept common deviations from the format
+    ///
+    /// The following deviations are accepted:
+    ///
+    /// - `→` arrow instead of `-->` in time line;
+    /// - a time line without arrow consisting of two times separated by whitespace, e.g. `00:00:01,000 00:00:02,000`;
+    /// - repeated or trailing commas in time, e.g. `00:00:01,,100` or `00:00:01,100,`;
+    /// - a dangling position without time at the end of input, it is ignored.
+    pub lenient: bool,
+}
+
+/// A separator of subtitle text lines
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub enum LineJoin {
+    /// `\n`
+    #[default]
+    Newline,
+    /// A single space
+    Space,
+    /// A custom separator, e.g. `\r\n`
+    Custom(String),
+}
+
+impl LineJoin {
+    fn separator(&self) -> &str {
+        match self {
+            LineJoin::Newline => "\n",
+            LineJoin::Space => " ",
+            LineJoin::Custom(separator) => separator,
+        }
+    }
+}
+
+/// A non-fatal problem found while parsing
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Diagnostic {
+    /// Subtitle position has been already used by a previous subtitle
+    DuplicateIndex {
+        /// Subtitle position
+        index: usize,
+        /// Number of line containing the position, starting from 1
+        line: usize,
+    },
+}
+
+impl fmt::Display for Diagnostic {
+    fn fmt(&self, out: &mut fmt::Formatter) -> fmt::Result {
+        use self::Diagnostic::*;
+        match self {
+            DuplicateIndex { index, line } => write!(out, "duplicate subtitle position {index} at line {line}"),
+        }
+    }
+}
+
+/// Subtitles parser builder
+///
+/// ```
+/// use std::io::Cursor;
+///
+/// let parser = srtparse::Parser::builder()
+///     .labels(true)
+///     .allow_empty_text(true)
+///     .build(Cursor::new("intro\n00:00:01,000 --> 00:00:02,000\n"));
+/// let items = parser.collect::<Result<Vec<_>, _>>().unwrap();
+/// assert_eq!(items[0].label.as_deref(), Some("intro"));
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct ParserBuilder {
+    options: ParserOptions,
+}
+
+impl ParserBuilder {
+    /// Sets [`ParserOptions::labels`]
+    pub fn labels(mut self, value: bool) -> Self {
+        self.options.labels = value;
+        self
+    }
+
+    /// Sets [`ParserOptions::allow_empty_text`]
+    pub fn allow_empty_text(mut self, value: bool) -> Self {
+        self.options.allow_empty_text = value;
+        self
+    }
+
+    /// Sets [`ParserOptions::auto_index`]
+    pub fn auto_index(mut self, value: bool) -> Self {
+        self.options.auto_index = value;
+        self
+    }
+
+    /// Sets [`ParserOptions::diagnostics`]
+    pub fn diagnostics(mut self, value: bool) -> Self {
+        self.options.diagnostics = value;
+        self
+    }
+
+    /// Sets [`ParserOptions::strict`]
+    pub fn strict(mut self, value: bool) -> Self {
+        self.options.strict = value;
+        self
+    }
+
+    /// Sets [`ParserOptions::preserve_text_whitespace`]
+    pub fn preserve_text_whitespace(mut self, value: bool) -> Self {
+        self.options.preserve_text_whitespace = value;
+        self
+    }
+
+    /// Sets [`ParserOptions::line_join`]
+    pub fn line_join(mut self, value: LineJoin) -> Self {
+        self.options.line_join = value;
+        self
+    }
+
+    /// Sets [`ParserOptions::lenient`]
+    pub fn lenient(mut self, value: bool) -> Self {
+        self.options.lenient = value;
+        self
+    }
+
+    /// Returns configured options
+    ///
+    /// Useful for `from_*_with` functions.
+    pub fn into_options(self) -> ParserOptions {
+        self.options
+    }
+
+    /// Creates a new parser from a buffered reader
+    #[cfg(feature = "std")]
+    pub fn build<B>(self, reader: B) -> Parser<B>
+    where
+        B: BufRead,
+    {
+        Parser::with_options(reader, self.options)
+    }
+}
 
 /// Subtitles parser
+///
+/// Positions are not required to be sequential, so concatenated files
+/// with restarting positions are parsed as a single list of subtitles.
+/// A numeric text line followed by a time line is considered to be a start of the next subtitle,
+/// thus a missing blank line between concatenated files does not merge subtitles.
+#[cfg(feature = "std")]
 pub struct Parser<B> {
     lines: Lines<B>,
-    state: State,
-    factory: ItemFactory,
+    machine: Machine,
 }
 
+#[cfg(feature = "std")]
+impl Parser<()> {
+    /// Creates a new parser builder
+    pub fn builder() -> ParserBuilder {
+        ParserBuilder::default()
+    }
+}
+
+#[cfg(feature = "std")]
 impl<B> Parser<B>
 where
     B: BufRead,
 {
     /// Creates a new parser from a buffered reader
     pub fn new(reader: B) -> Self {
+        Self::with_options(reader, ParserOptions::default())
+    }
+
+    /// Creates a new parser from a buffered reader using given options
+    pub fn with_options(reader: B, options: ParserOptions) -> Self {
         Parser {
             lines: reader.lines(),
-            state: State::Start,
-            factory: ItemFactory::default(),
+            machine: Machine::new(options),
         }
     }
 
+    /// Returns diagnostics collected so far
+    ///
+    /// Always empty unless [`ParserOptions::diagnostics`] is enabled.
+    pub fn diagnostics(&self) -> &[Diagnostic] {
+        self.machine.diagnostics()
+    }
+
+    /// Returns number of the last read line, starting from 1
+    pub fn line_number(&self) -> usize {
+        self.machine.line_number()
+    }
+
+    /// Skips the rest of a malformed subtitle after an error so parsing can continue
+    pub(crate) fn recover(&mut self) {
+        self.machine.recover();
+    }
+
     fn read_line(&mut self) -> Result<Option<String>, ParseError> {
         self.lines.next().transpose().map_err(ParseError::ReadLine)
     }
 
     fn parse_item(&mut self) -> Result<Option<Item>, ParseError> {
-        use self::State::*;
         loop {
-            match &self.state {
-                Start => {
-                    let line = match self.read_line()? {
-                        Some(line) => line,
-                        None => {
-                            return Ok(None);
-                        }
-                    };
-                    self.state = Pos(String::from(line.trim_start_matches(UTF8_BOM).trim()));
+            let line = if self.machine.needs_line() {
+                self.read_line()?
+            } else {
+                None
+            };
+            match self.machine.step(line)? {
+                Step::Continue => {}
+                Step::Item(item) => return Ok(Some(item)),
+                Step::Done => return Ok(None),
+            }
+        }
+    }
+}
+
+/// Subtitles parser driven by the caller
+///
+/// Unlike `Parser` it does not read input by itself, lines are fed one by one instead.
+/// This allows to parse subtitles coming from an arbitrary source, e.g. chunks of a stream.
+#[derive(Default)]
+pub struct PushParser {
+    machine: Machine,
+    items: Vec<Item>,
+}
+
+impl PushParser {
+    /// Creates a new parser
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates a new parser using given options
+    pub fn with_options(options: ParserOptions) -> Self {
+        PushParser {
+            machine: Machine::new(options),
+            items: Vec::new(),
+        }
+    }
+
+    /// Feeds a line without line terminator to the parser
+    ///
+    /// Parser should not be used after an error.
+    pub fn feed(&mut self, line: &str) -> Result<(), ParseError> {
+        self.drive(Some(String::from(line)))
+    }
+
+    /// Returns diagnostics collected so far
+    ///
+    /// Always empty unless [`ParserOptions::diagnostics`] is enabled.
+    pub fn diagnostics(&self) -> &[Diagnostic] {
+        self.machine.diagnostics()
+    }
+
+    /// Finishes parsing and returns all parsed items
+    pub fn finish(mut self) -> Result<Vec<Item>, ParseError> {
+        self.drive(None)?;
+        Ok(self.items)
+    }
+
+    fn drive(&mut self, mut line: Option<String>) -> Result<(), ParseError> {
+        loop {
+            match self.machine.step(line.take())? {
+                Step::Continue => {}
+                Step::Item(item) => self.items.push(item),
+                Step::Done => return Ok(()),
+            }
+            if self.machine.needs_line() {
+                return Ok(());
+            }
+        }
+    }
+}
+
+/// Parses a single subtitle block
+///
+/// Fails with [`ParseError::MultipleItems`] when input contains more than one subtitle.
+///
+/// ```
+/// let item = srtparse::Item::try_from("1\n00:00:01,000 --> 00:00:02,000\nHello").unwrap();
+/// assert_eq!(item.text, "Hello");
+/// ```
+impl TryFrom<&str> for Item {
+    type Error = ParseError;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        let mut parser = PushParser::new();
+        for line in value.lines() {
+            parser.feed(line)?;
+        }
+        let mut items = parser.finish()?;
+        match items.len() {
+            0 => Err(ParseError::UnexpectedEnd),
+            1 => Ok(items.remove(0)),
+            count => Err(ParseError::MultipleItems(count)),
+        }
+    }
+}
+
+/// Parses subtitles from arbitrary bytes never failing
+///
+/// Invalid UTF-8 sequences are replaced with `U+FFFD`, input is parsed with [`ParserOptions::lenient`]
+/// enabled and a malformed subtitle block is skipped instead of stopping the parsing.
+///
+/// ```
+/// let items = srtparse::parse_bytes_lossy(b"1\n00:00:01,000 --> 00:00:02,000\nHi \xff\n\ngarbage\n");
+/// assert_eq!(items.len(), 1);
+/// assert_eq!(items[0].text, "Hi \u{fffd}");
+/// ```
+pub fn parse_bytes_lossy(data: &[u8]) -> Vec<Item> {
+    let data = String::from_utf8_lossy(data);
+    let mut lines = data.lines();
+    let mut machine = Machine::new(ParserOptions {
+        lenient: true,
+        ..ParserOptions::default()
+    });
+    let mut items = Vec::new();
+    loop {
+        let line = if machine.needs_line() {
+            lines.next().map(String::from)
+        } else {
+            None
+        };
+        match machine.step(line) {
+            Ok(Step::Continue) => {}
+            Ok(Step::Item(item)) => items.push(item),
+            Ok(Step::Done) => return items,
+            Err(_) => machine.recover(),
+        }
+    }
+}
+
+/// A state machine shared by all parser frontends
+///
+/// Frontend feeds input lines to [`Machine::step`] while [`Machine::needs_line`] returns `true`.
+#[derive(Default)]
+pub(crate) struct Machine {
+    options: ParserOptions,
+    state: State,
+    factory: ItemFactory,
+    last_pos: usize,
+    last_start_time: Option<Time>,
+    line_number: usize,
+    next_block_start: usize,
+    item_lines: (usize, usize),
+    seen_positions: BTreeSet<usize>,
+    diagnostics: Vec<Diagnostic>,
+    resumable: bool,
+}
+
+/// A result of a single state machine step
+pub(crate) enum Step {
+    /// More input is required
+    Continue,
+    /// An item is ready
+    Item(Item),
+    /// Input is over
+    Done,
+}
+
+impl Machine {
+    pub(crate) fn new(options: ParserOptions) -> Self {
+        Machine {
+            options,
+            ..Default::default()
+        }
+    }
+
+    pub(crate) fn diagnostics(&self) -> &[Diagnostic] {
+        &self.diagnostics
+    }
+
+    fn time_delimiter(&self, line: &str) -> Option<&'static str> {
+        if line.contains(TIME_DELIMITER) {
+            Some(TIME_DELIMITER)
+        } else if self.options.lenient && line.contains(LENIENT_TIME_DELIMITER) {
+            Some(LENIENT_TIME_DELIMITER)
+        } else {
+            None
+        }
+    }
+
+    fn is_time_line(&self, line: &str) -> bool {
+        self.time_delimiter(line).is_some() || self.split_arrowless_time(line).is_some()
+    }
+
+    /// Splits a lenient time line without arrow into start and end times
+    ///
+    /// The line must consist of two valid times only.
+    fn split_arrowless_time<'a>(&self, line: &'a str) -> Option<(&'a str, &'a str)> {
+        if !self.options.lenient {
+            return None;
+        }
+        let mut parts = line.split_whitespace();
+        let (start, end) = (parts.next()?, parts.next()?);
+        let valid =
+            parts.next().is_none() && self.parse_time_value(start).is_ok() && self.parse_time_value(end).is_ok();
+        valid.then_some((start, end))
+    }
+
+    fn parse_time(&mut self, line: &str) -> Result<(), ParseError> {
+        if self.time_delimiter(line).is_none() {
+            if let Some((start, end)) = self.split_arrowless_time(line) {
+                self.factory
+                    .set_start_time(self.parse_time_value(start).map_err(ParseError::ParseTimeStart)?);
+                self.factory
+                    .set_end_time(self.parse_time_value(end).map_err(ParseError::ParseTimeEnd)?);
+                return Ok(());
+            }
+        }
+        let delimiter = self.time_delimiter(line).unwrap_or(TIME_DELIMITER);
+        let mut parts = line.trim().split(delimiter);
+        if let Some(v) = parts.next() {
+            self.factory
+                .set_start_time(self.parse_time_value(v).map_err(ParseError::ParseTimeStart)?);
+        }
+        if let Some(v) = parts.next() {
+            let (end_time, coordinates) = match v.trim().split_once(char::is_whitespace) {
+                Some((end_time, coordinates)) => (end_time, Some(coordinates)),
+                None => (v.trim(), None),
+            };
+            self.factory
+                .set_end_time(self.parse_time_value(end_time).map_err(ParseError::ParseTimeEnd)?);
+            if let Some(raw) = coordinates {
+                let coordinates =
+                    parse_coordinates(raw).ok_or_else(|| ParseError::BadCoordinates(String::from(raw)))?;
+                self.factory.set_coordinates(coordinates);
+            }
+        }
+        if let Some(part) = parts.next() {
+            return Err(ParseError::ExtraTimePart(String::from(part)));
+        }
+        Ok(())
+    }
+
+    fn parse_time_value(&self, raw: &str) -> Result<Time, ParseTimeError> {
+        let raw = raw.trim();
+        if self.options.lenient {
+            if let Some((time, fraction)) = raw.split_once(',') {
+                return format!("{time},{}", fraction.trim_matches(',')).parse();
+            }
+        }
+        raw.parse()
+    }
+
+    fn check_duplicate(&mut self, index: usize, line: usize) {
+        if self.options.diagnostics && !self.seen_positions.insert(index) {
+            self.diagnostics.push(Diagnostic::DuplicateIndex { index, line });
+        }
+    }
+
+    /// Handles a line of subtitle text
+    ///
+    /// A numeric line is kept aside until the next line is known:
+    /// when it is a time line, the number starts a new subtitle.
+    fn push_text(&mut self, line: &str) {
+        let trimmed = line.trim();
+        let text = if self.options.preserve_text_whitespace {
+            line
+        } else {
+            trimmed
+        };
+        if trimmed.is_empty() {
+            self.state = State::Blank;
+        } else if trimmed.bytes().all(|b| b.is_ascii_digit()) {
+            self.state = State::Number(String::from(text));
+        } else {
+            self.item_lines.1 = self.line_number;
+            self.factory.append_text(text, self.options.line_join.separator());
+        }
+    }
+
+    fn take_item(&mut self) -> Result<Item, ParseError> {
+        if self.options.allow_empty_text {
+            self.factory.ensure_text();
+        }
+        let item = self.factory.take()?;
+        if self.options.strict {
+            if item.end_time < item.start_time {
+                return Err(ParseError::ReversedTimes(item.pos));
+            }
+            if self.last_start_time.is_some_and(|time| item.start_time < time) {
+                return Err(ParseError::NonMonotonic(item.pos));
+            }
+            self.last_start_time = Some(item.start_time);
+        }
+        Ok(item)
+    }
+
+    /// Number of the last consumed line, starting from 1
+    #[cfg(feature = "std")]
+    pub(crate) fn line_number(&self) -> usize {
+        self.line_number
+    }
+
+    /// Numbers of the first and the last non-blank lines of an item
+    ///
+    /// Refers to the item returned by the last step or, after an error, to the one which failed to parse.
+    #[cfg(feature = "std")]
+    pub(crate) fn item_lines(&self) -> RangeInclusive<usize> {
+        self.item_lines.0..=self.item_lines.1
+    }
+
+    /// Discards the item being built after an error and skips the rest of its block
+    pub(crate) fn recover(&mut self) {
+        self.factory = ItemFactory::default();
+        if !core::mem::take(&mut self.resumable) && !matches!(self.state, State::Stop) {
+            self.state = State::Skip;
+        }
+    }
+
+    /// Whether the next step consumes an input line
+    pub(crate) fn needs_line(&self) -> bool {
+        !matches!(self.state, State::Pos(..) | State::Stop)
+    }
+
+    /// Advances the state machine
+    ///
+    /// `line` is `None` when input is over or when [`Machine::needs_line`] returns `false`.
+    pub(crate) fn step(&mut self, line: Option<String>) -> Result<Step, ParseError> {
+        use self::State::*;
+        let line = line.map(|mut line| {
+            self.line_number += 1;
+            while line.ends_with('\r') {
+                line.pop();
+            }
+            // Concatenated files may carry a BOM at the start of any line, not only the first one
+            if line.starts_with(UTF8_BOM) {
+                line.drain(..UTF8_BOM.len());
+            }
+            line
+        });
+        match &self.state {
+            Start => match line {
+                Some(line) => {
+                    self.next_block_start = self.line_number;
+                    self.state = Pos(String::from(line.trim()), None);
                 }
-                Pos(line) => {
-                    if self.factory.maybe_ready() {
-                        return Ok(Some(self.factory.take()?));
-                    }
-                    let pos = line.parse::<usize>().map_err(ParseError::BadPosition)?;
-                    self.factory.set_pos(pos);
-                    self.state = Time;
+                None => self.state = Stop,
+            },
+            Pos(line, time) => {
+                if self.factory.maybe_ready() {
+                    // The error belongs to the previous item, so current line still starts a new one
+                    self.resumable = true;
+                    let item = self.take_item()?;
+                    self.resumable = false;
+                    return Ok(Step::Item(item));
+                }
+                self.item_lines = (self.next_block_start, self.line_number);
+                if self.options.auto_index && self.is_time_line(line) {
+                    let line = line.clone();
+                    self.last_pos += 1;
+                    self.check_duplicate(self.last_pos, self.line_number);
+                    self.factory.set_pos(self.last_pos);
+                    self.parse_time(&line)?;
+                    self.state = Text;
+                    return Ok(Step::Continue);
                 }
-                Time => {
-                    let line = match self.read_line()? {
-                        Some(line) => line,
-                        None => return Err(ParseError::UnexpectedEnd),
-                    };
-                    let mut parts = line.trim().split(TIME_DELIMITER);
-                    if let Some(v) = parts.next() {
-                        self.factory
-                            .set_start_time(v.parse().map_err(ParseError::ParseTimeStart)?);
+                let pos = match line.parse::<usize>() {
+                    Ok(pos) => pos,
+                    Err(_) if self.options.labels && !line.is_empty() => {
+                        self.factory.set_label(line.clone());
+                        self.last_pos + 1
                     }
-                    if let Some(v) = parts.next() {
-                        self.factory.set_end_time(v.parse().map_err(ParseError::ParseTimeEnd)?);
+                    Err(err) => return Err(ParseError::BadPosition(err)),
+                };
+                let time = time.clone();
+                self.check_duplicate(pos, self.line_number - usize::from(time.is_some()));
+                self.factory.set_pos(pos);
+                self.last_pos = pos;
+                match time {
+                    Some(time) => {
+                        self.parse_time(&time)?;
+                        self.state = Text;
                     }
-                    if let Some(part) = parts.next() {
-                        return Err(ParseError::ExtraTimePart(String::from(part)));
+                    None => self.state = Time,
+                }
+            }
+            Time => {
+                let line = match line {
+                    Some(line) if line.trim().is_empty() => return Ok(Step::Continue),
+                    Some(line) => line,
+                    None if self.options.lenient => {
+                        self.factory = ItemFactory::default();
+                        self.state = Stop;
+                        return Ok(Step::Continue);
                     }
-                    self.state = Text;
+                    None => return Err(ParseError::UnexpectedEnd),
+                };
+                self.item_lines.1 = self.line_number;
+                self.parse_time(&line)?;
+                self.state = Text;
+            }
+            Text => match line {
+                Some(line) => self.push_text(&line),
+                None => {
+                    self.state = Stop;
+                    return Ok(Step::Item(self.take_item()?));
                 }
-                Text => match self.read_line()? {
+            },
+            Number(number) => {
+                let number = number.clone();
+                match line {
+                    Some(line) if self.is_time_line(&line) => {
+                        // The held back number starts the next subtitle
+                        self.next_block_start = self.line_number - 1;
+                        self.state = Pos(String::from(number.trim()), Some(line));
+                    }
                     Some(line) => {
-                        let line = line.trim();
-                        if line.is_empty() {
-                            match self.read_line()? {
-                                Some(line) => {
-                                    self.state = Pos(String::from(line.trim()));
-                                }
-                                None => {
-                                    self.state = Stop;
-                                    return Ok(Some(self.factory.take()?));
-                                }
-                            }
-                        } else {
-                            self.factory.append_text(line);
-                        }
+                        self.factory.append_text(&number, self.options.line_join.separator());
+                        self.item_lines.1 = self.line_number - 1;
+                        self.state = Text;
+                        self.push_text(&line);
                     }
                     None => {
+                        self.factory.append_text(&number, self.options.line_join.separator());
+                        self.item_lines.1 = self.line_number;
                         self.state = Stop;
-                        return Ok(Some(self.factory.take()?));
+                        return Ok(Step::Item(self.take_item()?));
                     }
-                },
-                Stop => return Ok(None),
+                }
             }
+            Blank => match line {
+                Some(line) if line.trim().is_empty() => {}
+                Some(line) => {
+                    self.next_block_start = self.line_number;
+                    self.state = Pos(String::from(line.trim()), None);
+                }
+                // Factory is empty when the previous block was skipped after an error
+                None if !self.factory.maybe_ready() => self.state = Stop,
+                None => {
+                    self.state = Stop;
+                    return Ok(Step::Item(self.take_item()?));
+                }
+            },
+            Skip => match line {
+                Some(line) if line.trim().is_empty() => self.state = Blank,
+                Some(_) => {}
+                None => self.state = Stop,
+            },
+            Stop => return Ok(Step::Done),
+        }
+        Ok(Step::Continue)
+    }
+}
+
+fn parse_coordinates(raw: &str) -> Option<Coordinates> {
+    let (mut x1, mut x2, mut y1, mut y2) = (None, None, None, None);
+    for token in raw.split_whitespace() {
+        let (key, value) = token.split_once(':')?;
+        let target = match key {
+            "X1" | "x1" => &mut x1,
+            "X2" | "x2" => &mut x2,
+            "Y1" | "y1" => &mut y1,
+            "Y2" | "y2" => &mut y2,
+            _ => return None,
+        };
+        if target.replace(value.parse().ok()?).is_some() {
+            return None;
         }
     }
+    Some(Coordinates {
+        x1: x1?,
+        x2: x2?,
+        y1: y1?,
+        y2: y2?,
+    })
 }
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Default)]
 enum State {
+    #[default]
     Start,
-    Pos(String),
+    Pos(String, Option<String>),
     Time,
     Text,
+    Number(String),
+    Blank,
+    Skip,
     Stop,
 }
 
+#[cfg(feature = "std")]
 impl<B> Iterator for Parser<B>
 where
     B: BufRead,
@@ -126,6 +729,8 @@ where
 /// An error when parsing a subtitle
 #[derive(Debug)]
 pub enum ParseError {
+    /// Could not parse coordinates after the end time
+    BadCoordinates(String),
     /// An error when parsing subtitle position
     BadPosition(ParseIntError),
     /// Can not create subtitle item
@@ -136,7 +741,14 @@ pub enum ParseError {
     ParseTimeStart(ParseTimeError),
     /// Could not parse end time
     ParseTimeEnd(ParseTimeError),
+    /// Input contains the given number of subtitles while a single one is expected
+    MultipleItems(usize),
+    /// Subtitle with the given position starts before the previous one
+    NonMonotonic(usize),
+    /// Subtitle with the given position ends before it starts
+    ReversedTimes(usize),
     /// Could not read a line
+    #[cfg(feature = "std")]
     ReadLine(IoError),
     /// Input ends unexpectedly
     UnexpectedEnd,
@@ -152,6 +764,7 @@ impl fmt::Display for ParseError {
     fn fmt(&self, out: &mut fmt::Formatter) -> fmt::Result {
         use self::ParseError::*;
         match self {
+            BadCoordinates(raw) => write!(out, "bad subtitle coordinates: '{raw}'"),
             BadPosition(err) => write!(out, "bad subtitle position: {err}"),
             CreateSubtitle(err) => write!(out, "{err}"),
             ExtraTimePart(part) => write!(
@@ -160,6 +773,10 @@ impl fmt::Display for ParseError {
             ),
             ParseTimeStart(err) => write!(out, "failed to parse start time: {err}"),
             ParseTimeEnd(err) => write!(out, "failed to parse end time: {err}"),
+            MultipleItems(count) => write!(out, "expected a single subtitle, found {count}"),
+            NonMonotonic(pos) => write!(out, "subtitle {pos} starts before the previous one"),
+            ReversedTimes(pos) => write!(out, "subtitle {pos} ends before it starts"),
+            #[cfg(feature = "std")]
             ReadLine(err) => write!(out, "could not read a line from input: {err}"),
             UnexpectedEnd => write!(out, "unexpected end of input"),
         }
@@ -170,11 +787,16 @@ impl Error for ParseError {
     fn source(&self) -> Option<&(dyn Error + 'static)> {
         use self::ParseError::*;
         Some(match self {
+            BadCoordinates(_raw) => return None,
             BadPosition(err) => err,
             CreateSubtitle(err) => err,
             ExtraTimePart(_part) => return None,
             ParseTimeStart(err) => err,
             ParseTimeEnd(err) => err,
+            MultipleItems(_count) => return None,
+            NonMonotonic(_pos) => return None,
+            ReversedTimes(_pos) => return None,
+            #[cfg(feature = "std")]
             ReadLine(err) => err,
             UnexpectedEnd => return None,
         })
@@ -192,6 +814,11 @@ mod tests {
         parser.map(|x| x.unwrap()).collect()
     }
 
+    fn parse_ok_with(data: &str, options: ParserOptions) -> Vec<Item> {
+        let parser = Parser::with_options(Cursor::new(data), options);
+        parser.map(|x| x.unwrap()).collect()
+    }
+
     fn parse_err(data: &str) -> String {
         let mut parser = Parser::new(Cursor::new(data));
         parser.next().unwrap().unwrap_err().to_string()
@@ -238,7 +865,9 @@ Soon, Marcus will take the throne.
                         seconds: 2,
                         milliseconds: 563
                     },
-                    text: String::from("The war had all but ground to a halt\nin the blink of an eye.")
+                    text: String::from("The war had all but ground to a halt\nin the blink of an eye."),
+                    label: None,
+                    coordinates: None,
                 }
             );
 
@@ -258,7 +887,9 @@ Soon, Marcus will take the throne.
                         seconds: 8,
                         milliseconds: 986
                     },
-                    text: String::from("Lucian, the most feared and ruthless\nleader ever to rule the Lycan clan...")
+                    text: String::from("Lucian, the most feared and ruthless\nleader ever to rule the Lycan clan..."),
+                    label: None,
+                    coordinates: None,
                 }
             );
 
@@ -278,7 +909,9 @@ Soon, Marcus will take the throne.
                         seconds: 11,
                         milliseconds: 656
                     },
-                    text: String::from("...had finally been killed.")
+                    text: String::from("...had finally been killed."),
+                    label: None,
+                    coordinates: None,
                 }
             );
 
@@ -298,7 +931,9 @@ Soon, Marcus will take the throne.
                         seconds: 6,
                         milliseconds: 162
                     },
-                    text: String::from("Soon, Marcus will take the throne.")
+                    text: String::from("Soon, Marcus will take the throne."),
+                    label: None,
+                    coordinates: None,
                 }
             );
         }
@@ -308,6 +943,259 @@ Soon, Marcus will take the throne.
         assert_eq!(parse_ok("").len(), 0);
     }
 
+    #[test]
+    fn it_parses_crlf() {
+        let source = "1\n00:00:01,000 --> 00:00:02,000\nfirst\nline\n\n2\n00:00:03,000 --> 00:00:04,000\nsecond\n";
+        let expected = parse_ok(source);
+        assert_eq!(expected.len(), 2);
+        assert_eq!(parse_ok(&source.replace('\n', "\r\n")), expected);
+        assert_eq!(parse_ok(&source.replace('\n', "\r\r\n")), expected);
+    }
+
+    #[test]
+    fn it_skips_blank_lines_between_items() {
+        let result =
+            parse_ok("1\n00:00:01,000 --> 00:00:02,000\nfirst\n\n\n\n2\n00:00:03,000 --> 00:00:04,000\nsecond\n");
+        assert_eq!(result.len(), 2);
+        assert_eq!(result[0].text, "first");
+        assert_eq!(result[1].pos, 2);
+        assert_eq!(result[1].text, "second");
+        let result =
+            parse_ok("1\n00:00:01,000 --> 00:00:02,000\nfirst\n \n\t\n\n2\n00:00:03,000 --> 00:00:04,000\nsecond\n");
+        assert_eq!(result.len(), 2);
+    }
+
+    #[test]
+    fn it_ignores_trailing_blank_lines() {
+        let result = parse_ok("1\n00:00:01,000 --> 00:00:02,000\ntext\n   \n\n");
+        assert_eq!(result.len(), 1);
+        assert_eq!(result[0].text, "text");
+        let result = parse_ok("1\n00:00:01,000 --> 00:00:02,000\ntext  \n \t \n");
+        assert_eq!(result.len(), 1);
+        assert_eq!(result[0].text, "text");
+    }
+
+    #[test]
+    fn it_parses_concatenated_files() {
+        let first = "1\n00:00:01,000 --> 00:00:02,000\nfirst\n\n2\n00:00:03,000 --> 00:00:04,000\nsecond\n42";
+        let second = "1\n00:00:05,000 --> 00:00:06,000\nthird\n\n2\n00:00:07,000 --> 00:00:08,000\nfourth\n";
+        for separator in ["\n\n", "\n"] {
+            let result = parse_ok(&format!("{first}{separator}{second}"));
+            assert_eq!(result.iter().map(|x| x.pos).collect::<Vec<_>>(), vec![1, 2, 1, 2]);
+            assert_eq!(
+                result.iter().map(|x| x.text.as_str()).collect::<Vec<_>>(),
+                vec!["first", "second\n42", "third", "fourth"]
+            );
+        }
+        let result =
+            parse_ok("1\n00:00:01,000 --> 00:00:02,000\nnumber\n42\n7\n\n2\n00:00:03,000 --> 00:00:04,000\n42");
+        assert_eq!(result[0].text, "number\n42\n7");
+        assert_eq!(result[1].text, "42");
+    }
+
+    #[test]
+    fn it_skips_blank_lines_before_time() {
+        let result = parse_ok("1\n\n00:00:01,000 --> 00:00:02,000\nhi");
+        assert_eq!(
+            result,
+            vec![Item {
+                pos: 1,
+                start_time: Time {
+                    hours: 0,
+                    minutes: 0,
+                    seconds: 1,
+                    milliseconds: 0
+                },
+                end_time: Time {
+                    hours: 0,
+                    minutes: 0,
+                    seconds: 2,
+                    milliseconds: 0
+                },
+                text: String::from("hi"),
+                label: None,
+                coordinates: None,
+            }]
+        );
+    }
+
+    #[test]
+    fn it_accepts_whitespace_variants_around_arrow() {
+        let expected = parse_ok("1\n00:00:01,000 --> 00:00:02,000\nhi");
+        for line in [
+            "00:00:01,000-->00:00:02,000",
+            "00:00:01,000  -->  00:00:02,000",
+            "00:00:01,000\t-->\t00:00:02,000",
+            " \t00:00:01,000 \t--> \t00:00:02,000\t ",
+            "00:00:01,000\u{a0}-->\u{a0}00:00:02,000",
+        ] {
+            assert_eq!(parse_ok(&format!("1\n{line}\nhi")), expected);
+        }
+    }
+
+    #[test]
+    fn it_joins_lines() {
+        let source = "1\n00:00:01,000 --> 00:00:02,000\na\nb\n\n2\n00:00:03,000 --> 00:00:04,000\nc\n42\nd\n";
+        let join = |line_join| {
+            let options = ParserOptions {
+                line_join,
+                ..Default::default()
+            };
+            parse_ok_with(source, options)
+                .into_iter()
+                .map(|item| item.text)
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(join(LineJoin::Newline), vec!["a\nb", "c\n42\nd"]);
+        assert_eq!(join(LineJoin::Space), vec!["a b", "c 42 d"]);
+        assert_eq!(
+            join(LineJoin::Custom(String::from("\r\n"))),
+            vec!["a\r\nb", "c\r\n42\r\nd"]
+        );
+    }
+
+    #[test]
+    fn it_preserves_text_whitespace() {
+        let source = "1\n00:00:01,000 --> 00:00:02,000\n  indented\ntrailing \n 42\n\n2\n00:00:03,000 --> 00:00:04,000\n\tlast\r\n";
+        let options = ParserOptions {
+            preserve_text_whitespace: true,
+            ..Default::default()
+        };
+        let result = parse_ok_with(source, options);
+        assert_eq!(result.len(), 2);
+        assert_eq!(result[0].text, "  indented\ntrailing \n 42");
+        assert_eq!(result[1].text, "\tlast");
+        let result = parse_ok(source);
+        assert_eq!(result[0].text, "indented\ntrailing\n42");
+        assert_eq!(result[1].text, "last");
+    }
+
+    #[test]
+    fn it_ignores_dangling_position_when_lenient() {
+        let options = || ParserOptions {
+            lenient: true,
+            ..Default::default()
+        };
+        for source in [
+            "1\n00:00:01,000 --> 00:00:02,000\nhi\n\n2\n",
+            "1\n00:00:01,000 --> 00:00:02,000\nhi\n\n2\n\n\n",
+            "1\n00:00:01,000 --> 00:00:02,000\nhi\n\n2",
+        ] {
+            let result = parse_ok_with(source, options());
+            assert_eq!(result.len(), 1);
+            assert_eq!(result[0].text, "hi");
+            let err = Parser::new(Cursor::new(source))
+                .collect::<Result<Vec<_>, _>>()
+                .unwrap_err();
+            assert!(matches!(err, ParseError::UnexpectedEnd));
+        }
+    }
+
+    #[test]
+    fn it_accepts_extra_commas_when_lenient() {
+        let expected = parse_ok("1\n00:00:01,100 --> 00:00:02,200\nhi");
+        for line in [
+            "00:00:01,,100 --> 00:00:02,,200",
+            "00:00:01,100, --> 00:00:02,200,",
+            "00:00:01,,100, --> 00:00:02,200",
+        ] {
+            let source = format!("1\n{line}\nhi");
+            let options = ParserOptions {
+                lenient: true,
+                ..Default::default()
+            };
+            assert_eq!(parse_ok_with(&source, options), expected);
+            assert!(parse_err(&source).starts_with("failed to parse"));
+        }
+    }
+
+    #[test]
+    fn it_accepts_time_without_arrow_when_lenient() {
+        let expected =
+            parse_ok("1\n00:00:01,000 --> 00:00:02,000\nhi\n12:00 is noon\n\n2\n00:00:03,000 --> 00:00:04,000\nbye");
+        let source = "1\n00:00:01,000 00:00:02,000\nhi\n12:00 is noon\n2\n00:00:03,000\t00:00:04,000\nbye";
+        let options = ParserOptions {
+            lenient: true,
+            ..Default::default()
+        };
+        assert_eq!(parse_ok_with(source, options), expected);
+        assert!(parse_err(source).starts_with("failed to parse start time"));
+    }
+
+    #[test]
+    fn it_accepts_unicode_arrow_when_lenient() {
+        let expected = parse_ok("1\n00:00:01,000 --> 00:00:02,000\nhi\n\n2\n00:00:03,000 --> 00:00:04,000\nbye");
+        let options = || ParserOptions {
+            lenient: true,
+            ..Default::default()
+        };
+        for arrow in ["→", " → ", "\u{a0}→\u{a0}"] {
+            let source = format!("1\n00:00:01,000{arrow}00:00:02,000\nhi\n2\n00:00:03,000{arrow}00:00:04,000\nbye");
+            assert_eq!(parse_ok_with(&source, options()), expected);
+        }
+        assert!(parse_err("1\n00:00:01,000 → 00:00:02,000\nhi").starts_with("failed to parse start time"));
+    }
+
+    #[test]
+    fn it_keeps_labels() {
+        let options = ParserOptions {
+            labels: true,
+            ..Default::default()
+        };
+        let result = parse_ok_with(
+            "1\n00:00:01,000 --> 00:00:02,000\nhello\n\nintro\n00:00:03,000 --> 00:00:04,000\nhi",
+            options,
+        );
+        assert_eq!(result.len(), 2);
+        assert_eq!(result[0].pos, 1);
+        assert_eq!(result[0].label, None);
+        assert_eq!(result[1].pos, 2);
+        assert_eq!(result[1].label.as_deref(), Some("intro"));
+        assert_eq!(result[1].text, "hi");
+        assert_eq!(
+            parse_err("intro"),
+            "bad subtitle position: invalid digit found in string"
+        );
+    }
+
+    #[test]
+    fn it_allows_empty_text() {
+        let options = ParserOptions {
+            allow_empty_text: true,
+            ..Default::default()
+        };
+        let source = "1\n00:00:01,000 --> 00:00:02,000\n\n2\n00:00:03,000 --> 00:00:04,000\nhi\n\n3\n00:00:05,000 --> 00:00:06,000";
+        let result = parse_ok_with(source, options);
+        assert_eq!(result.len(), 3);
+        assert_eq!(result[0].text, "");
+        assert_eq!(result[1].text, "hi");
+        assert_eq!(result[2].text, "");
+        assert_eq!(parse_err(source), "item text is missing");
+    }
+
+    #[test]
+    fn it_assigns_missing_positions() {
+        let options = ParserOptions {
+            auto_index: true,
+            ..Default::default()
+        };
+        let source = "00:00:01,000 --> 00:00:02,000\nfirst\n\n00:00:03,000 --> 00:00:04,000\nsecond\n\n00:00:05,000 --> 00:00:06,000\nthird\n";
+        let result = parse_ok_with(source, options.clone());
+        assert_eq!(result.iter().map(|x| x.pos).collect::<Vec<_>>(), vec![1, 2, 3]);
+        assert_eq!(result[1].text, "second");
+        assert_eq!(result[1].start_time.into_duration().as_secs(), 3);
+        assert_eq!(result[2].end_time.into_duration().as_secs(), 6);
+        let result = parse_ok_with(
+            "5\n00:00:01,000 --> 00:00:02,000\nfirst\n\n00:00:03,000 --> 00:00:04,000\nsecond\n",
+            options,
+        );
+        assert_eq!(result.iter().map(|x| x.pos).collect::<Vec<_>>(), vec![5, 6]);
+        assert_eq!(
+            parse_err(source),
+            "bad subtitle position: invalid digit found in string"
+        );
+    }
+
     #[test]
     fn it_fails_with_bad_position() {
         let err = parse_err("bad position");
@@ -332,6 +1220,85 @@ Soon, Marcus will take the throne.
         );
     }
 
+    #[test]
+    fn it_parses_coordinates() {
+        let source = "1\n00:00:01,000 --> 00:00:02,000 X1:100 X2:200 Y1:300 Y2:400\nhi\n\n2\n00:00:03,000 --> 00:00:04,000  \nbye";
+        let result = parse_ok(source);
+        assert_eq!(
+            result[0].coordinates,
+            Some(Coordinates {
+                x1: 100,
+                x2: 200,
+                y1: 300,
+                y2: 400
+            })
+        );
+        assert_eq!(result[1].coordinates, None);
+        assert_eq!(
+            result[0].to_string(),
+            "1\n00:00:01,000-->00:00:02,000 X1:100 X2:200 Y1:300 Y2:400\nhi"
+        );
+        assert_eq!(parse_ok(&crate::writer::to_string(&result)), result);
+    }
+
+    #[test]
+    fn it_fails_with_bad_coordinates() {
+        for coordinates in [
+            "X1:100 X2:200 Y1:300",
+            "X1:100 X2:200 Y1:300 Y2:abc",
+            "X1:1 X1:2 Y1:3 Y2:4",
+            "Z:1",
+        ] {
+            let err = parse_err(&format!("1\n00:00:01,000 --> 00:00:02,000 {coordinates}\nhi"));
+            assert_eq!(err, format!("bad subtitle coordinates: '{coordinates}'"));
+        }
+    }
+
+    #[test]
+    fn it_fails_with_reversed_times_when_strict() {
+        let source = "1\n00:00:01,000 --> 00:00:02,000\nhi\n\n2\n00:00:04,000 --> 00:00:03,000\nbye";
+        let options = ParserOptions {
+            strict: true,
+            ..Default::default()
+        };
+        let err = Parser::with_options(Cursor::new(source), options)
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap_err();
+        assert!(matches!(err, ParseError::ReversedTimes(2)));
+        assert_eq!(err.to_string(), "subtitle 2 ends before it starts");
+        assert_eq!(parse_ok(source).len(), 2);
+    }
+
+    #[test]
+    fn it_fails_with_non_monotonic_times_when_strict() {
+        let source = "1\n00:00:05,000 --> 00:00:06,000\nhi\n\n2\n00:00:05,000 --> 00:00:07,000\nsame\n\n3\n00:00:04,000 --> 00:00:05,000\nbye";
+        let options = ParserOptions {
+            strict: true,
+            ..Default::default()
+        };
+        let mut parser = Parser::with_options(Cursor::new(source), options);
+        assert_eq!(parser.next().unwrap().unwrap().pos, 1);
+        assert_eq!(parser.next().unwrap().unwrap().pos, 2);
+        let err = parser.next().unwrap().unwrap_err();
+        assert!(matches!(err, ParseError::NonMonotonic(3)));
+        assert_eq!(err.to_string(), "subtitle 3 starts before the previous one");
+        assert_eq!(parse_ok(source).len(), 3);
+    }
+
+    #[test]
+    fn it_parses_single_item() {
+        let item = Item::try_from("1\r\n00:00:01,000 --> 00:00:02,000\r\nfirst\r\nline\r\n\r\n").unwrap();
+        assert_eq!(item, parse_ok("1\n00:00:01,000 --> 00:00:02,000\nfirst\nline")[0]);
+
+        let err = Item::try_from("1\n00:00:01,000 --> 00:00:02,000\nfirst\n\n2\n00:00:03,000 --> 00:00:04,000\nsecond")
+            .unwrap_err();
+        assert!(matches!(err, ParseError::MultipleItems(2)));
+        assert_eq!(err.to_string(), "expected a single subtitle, found 2");
+
+        assert!(matches!(Item::try_from(""), Err(ParseError::UnexpectedEnd)));
+        assert!(matches!(Item::try_from("1\nbad"), Err(ParseError::ParseTimeStart(_))));
+    }
+
     #[test]
     fn it_fails_with_bad_time_format() {
         let err = parse_err("1\n00:00:00:00");
@@ -364,4 +1331,153 @@ Soon, Marcus will take the throne.
         let err = parse_err("1\n00:00:58,392 --> 00:01:02,563");
         assert_eq!(err, "item text is missing");
     }
+
+    #[test]
+    fn push_parser() {
+        let data = std::fs::read_to_string("./data/underworld.srt").unwrap();
+        let mut parser = PushParser::new();
+        for line in data.split('\n') {
+            parser.feed(line).unwrap();
+        }
+        let result = parser.finish().unwrap();
+        assert_eq!(result.len(), 706);
+        assert_eq!(result, crate::from_str(&data).unwrap());
+
+        let mut parser = PushParser::new();
+        parser.feed("1").unwrap();
+        assert_eq!(parser.finish().unwrap_err().to_string(), "unexpected end of input");
+
+        let mut parser = PushParser::new();
+        assert_eq!(
+            parser.feed("bad position").unwrap_err().to_string(),
+            "bad subtitle position: invalid digit found in string"
+        );
+    }
+
+    #[test]
+    fn builder() {
+        let source = "1\n00:00:01,000 --> 00:00:02,000\n\nintro\n00:00:03,000 --> 00:00:04,000\nhi";
+        let builder = Parser::builder().labels(true).allow_empty_text(true);
+        let result = builder
+            .clone()
+            .build(Cursor::new(source))
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap();
+        assert_eq!(result.len(), 2);
+        assert_eq!(result[0].text, "");
+        assert_eq!(result[1].pos, 2);
+        assert_eq!(result[1].label.as_deref(), Some("intro"));
+        assert_eq!(crate::from_str_with(source, builder.into_options()).unwrap(), result);
+    }
+
+    #[test]
+    fn diagnostics() {
+        let source = "1\n00:00:01,000 --> 00:00:02,000\na\n\n2\n00:00:03,000 --> 00:00:04,000\nb\n\n2\n00:00:05,000 --> 00:00:06,000\nc\n\n3\n00:00:07,000 --> 00:00:08,000\nd\n";
+        let mut parser = Parser::builder().diagnostics(true).build(Cursor::new(source));
+        assert_eq!(parser.by_ref().collect::<Result<Vec<_>, _>>().unwrap().len(), 4);
+        assert_eq!(
+            parser.diagnostics(),
+            &[Diagnostic::DuplicateIndex { index: 2, line: 9 }]
+        );
+        assert_eq!(
+            parser.diagnostics()[0].to_string(),
+            "duplicate subtitle position 2 at line 9"
+        );
+
+        let mut parser = Parser::new(Cursor::new(source));
+        assert_eq!(parser.by_ref().collect::<Result<Vec<_>, _>>().unwrap().len(), 4);
+        assert!(parser.diagnostics().is_empty());
+    }
+
+    #[test]
+    fn diagnostics_auto_index() {
+        let source = "00:00:01,000 --> 00:00:02,000\na\n\n1\n00:00:03,000 --> 00:00:04,000\nb\n";
+        let mut parser = Parser::builder()
+            .auto_index(true)
+            .diagnostics(true)
+            .build(Cursor::new(source));
+        assert_eq!(parser.by_ref().collect::<Result<Vec<_>, _>>().unwrap().len(), 2);
+        assert_eq!(
+            parser.diagnostics(),
+            &[Diagnostic::DuplicateIndex { index: 1, line: 4 }]
+        );
+    }
+
+    #[test]
+    fn bytes_lossy() {
+        let mut data = Vec::from(&b"1\n00:00:01,000 --> 00:00:02,000\nfirst\n\n2\nbroken\ntext\n\n"[..]);
+        data.extend_from_slice(b"3\n00:00:03,000 --> 00:00:04,000\nthird \xe2\x82\n\n4\n00:00:05,000 --> 00:00:");
+        let items = parse_bytes_lossy(&data);
+        assert_eq!(items.len(), 2);
+        assert_eq!(items[0].text, "first");
+        assert_eq!(items[1].pos, 3);
+        assert_eq!(items[1].text, "third \u{fffd}");
+    }
+
+    #[test]
+    fn bytes_lossy_random() {
+        let source = include_bytes!("../data/underworld.srt");
+        let mut seed = 0x2545_f491_4f6c_dd1du64;
+        let mut next = move || {
+            seed ^= seed << 13;
+            seed ^= seed >> 7;
+            seed ^= seed << 17;
+            seed
+        };
+        for _ in 0..200 {
+            let len = (next() % 512) as usize;
+            let data: Vec<u8> = (0..len).map(|_| next() as u8).collect();
+            parse_bytes_lossy(&data);
+            let mut data = Vec::from(&source[..2048]);
+            for _ in 0..16 {
+                let idx = (next() % data.len() as u64) as usize;
+                data[idx] = next() as u8;
+            }
+            let cut = (next() % data.len() as u64) as usize;
+            parse_bytes_lossy(&data[..cut]);
+        }
+        assert_eq!(parse_bytes_lossy(source).len(), 706);
+    }
+
+    #[test]
+    fn no_blank_lines() {
+        let data = "1\n00:00:01,000 --> 00:00:02,000\nfirst\n2\n00:00:03,000 --> 00:00:04,000\nsecond\n42\nthe answer";
+        let items = parse_ok(data);
+        assert_eq!(items.len(), 2);
+        assert_eq!(items[0].text, "first");
+        assert_eq!(items[1].pos, 2);
+        assert_eq!(items[1].text, "second\n42\nthe answer");
+    }
+
+    #[test]
+    fn no_blank_lines_lenient() {
+        let data = "1\n00:00:01,000 --> 00:00:02,000\nfirst\n2\n00:00:03,000 → 00:00:04,000\nsecond\n42\n\
+                    3\n00:00:05,000 00:00:06,000\nthird";
+        let items = parse_ok_with(
+            data,
+            ParserOptions {
+                lenient: true,
+                ..ParserOptions::default()
+            },
+        );
+        assert_eq!(items.len(), 3);
+        assert_eq!(items[0].text, "first");
+        assert_eq!(items[1].pos, 2);
+        assert_eq!(items[1].text, "second\n42");
+        assert_eq!(items[2].pos, 3);
+        assert_eq!(items[2].text, "third");
+    }
+
+    #[test]
+    fn mid_file_bom() {
+        let data = format!(
+            "{UTF8_BOM}1\n00:00:01,000 --> 00:00:02,000\nfirst\n\n{UTF8_BOM}1\n00:00:03,000 --> 00:00:04,000\nsecond\n\
+             {UTF8_BOM}2\n00:00:05,000 --> 00:00:06,000\nthird"
+        );
+        let items = parse_ok(&data);
+        assert_eq!(items.len(), 3);
+        assert_eq!(items[1].text, "second");
+        assert_eq!(items[2].pos, 2);
+        assert_eq!(items[2].text, "third");
+    }
 }
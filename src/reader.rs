@@ -1,13 +1,14 @@
 use crate::{
     item::Item,
-    parser::{ParseError, Parser},
+    parser::{Machine, ParseError, Parser, ParserOptions, Step, UTF8_BOM},
 };
 use std::{
     error::Error,
     fmt,
     fs::File,
-    io::{BufRead, BufReader, Cursor, Error as IoError},
-    path::Path,
+    io::{BufRead, BufReader, Cursor, Error as IoError, Read},
+    ops::Range,
+    path::{Path, PathBuf},
 };
 
 /// Read subtitles from a string
@@ -15,37 +16,441 @@ pub fn from_str<S>(input: S) -> Result<Vec<Item>, ReaderError>
 where
     S: AsRef<[u8]>,
 {
-    from_reader(Cursor::new(input))
+    from_str_with(input, ParserOptions::default())
+}
+
+/// Read subtitles from a string using given parser options
+pub fn from_str_with<S>(input: S, options: ParserOptions) -> Result<Vec<Item>, ReaderError>
+where
+    S: AsRef<[u8]>,
+{
+    let capacity = estimate_capacity(input.as_ref().len());
+    read_items(Cursor::new(input), options, capacity, usize::MAX)
+}
+
+/// Parse subtitles from a string lazily
+///
+/// Unlike [`from_str`], subtitles are not collected, so only the consumed ones are parsed.
+///
+/// ```
+/// let data = "1\n00:00:01,000 --> 00:00:02,000\nfirst\n\n2\nbad time\n";
+/// let first = srtparse::iter_str(data).take(1).collect::<Result<Vec<_>, _>>().unwrap();
+/// assert_eq!(first[0].text, "first");
+/// ```
+pub fn iter_str(input: &str) -> impl Iterator<Item = Result<Item, ParseError>> + '_ {
+    Parser::new(Cursor::new(input))
 }
 
 /// Read subtitles from a file
 pub fn from_file(path: impl AsRef<Path>) -> Result<Vec<Item>, ReaderError> {
-    from_reader(BufReader::new(File::open(path).map_err(ReaderError::OpenFile)?))
+    from_file_with(path, ParserOptions::default())
+}
+
+/// Read subtitles from a file using given parser options
+pub fn from_file_with(path: impl AsRef<Path>, options: ParserOptions) -> Result<Vec<Item>, ReaderError> {
+    let file = File::open(path).map_err(ReaderError::OpenFile)?;
+    let capacity = file
+        .metadata()
+        .map(|metadata| estimate_capacity(metadata.len() as usize))
+        .unwrap_or_default();
+    read_items(BufReader::new(file), options, capacity, usize::MAX)
+}
+
+/// Read subtitles from a gzip-compressed file
+#[cfg(feature = "gzip")]
+pub fn from_gz_file(path: impl AsRef<Path>) -> Result<Vec<Item>, ReaderError> {
+    from_gz_file_with(path, ParserOptions::default())
+}
+
+/// Read subtitles from a gzip-compressed file using given parser options
+#[cfg(feature = "gzip")]
+pub fn from_gz_file_with(path: impl AsRef<Path>, options: ParserOptions) -> Result<Vec<Item>, ReaderError> {
+    let file = File::open(path).map_err(ReaderError::OpenFile)?;
+    from_reader_with(BufReader::new(flate2::read::GzDecoder::new(file)), options)
+}
+
+/// A path of a file along with a result of reading subtitles from it
+pub type FileResult = (PathBuf, Result<Vec<Item>, ReaderError>);
+
+/// Read subtitles from every `.srt` file in a directory
+///
+/// Subdirectories are not visited. A failure to read a file does not stop reading others,
+/// so each path is returned along with its own result. Results are sorted by path.
+pub fn from_dir(path: impl AsRef<Path>) -> Result<Vec<FileResult>, ReaderError> {
+    let mut result = Vec::new();
+    read_dir(path.as_ref(), &["srt"], &mut result, None)?;
+    result.sort_by(|(a, _), (b, _)| a.cmp(b));
+    Ok(result)
+}
+
+/// Read subtitles from files in a directory and its subdirectories
+///
+/// Only files with one of given `extensions` (compared case-insensitively, without a leading dot) are read.
+/// Symbolic links to directories are not followed.
+/// A failure to read a file or a subdirectory does not stop reading others,
+/// so each path is returned along with its own result. Results are sorted by path.
+pub fn from_dir_recursive(root: impl AsRef<Path>, extensions: &[&str]) -> Result<Vec<FileResult>, ReaderError> {
+    let mut result = Vec::new();
+    let mut dirs = Vec::new();
+    read_dir(root.as_ref(), extensions, &mut result, Some(&mut dirs))?;
+    while let Some(dir) = dirs.pop() {
+        if let Err(err) = read_dir(&dir, extensions, &mut result, Some(&mut dirs)) {
+            result.push((dir, Err(err)));
+        }
+    }
+    result.sort_by(|(a, _), (b, _)| a.cmp(b));
+    Ok(result)
+}
+
+fn read_dir(
+    path: &Path,
+    extensions: &[&str],
+    result: &mut Vec<FileResult>,
+    mut dirs: Option<&mut Vec<PathBuf>>,
+) -> Result<(), ReaderError> {
+    for entry in path.read_dir().map_err(ReaderError::ReadDir)? {
+        let entry = entry.map_err(ReaderError::ReadDir)?;
+        let path = entry.path();
+        if let Some(dirs) = dirs.as_mut() {
+            if entry.file_type().is_ok_and(|file_type| file_type.is_dir()) {
+                dirs.push(path);
+                continue;
+            }
+        }
+        if path.is_file() && has_extension(&path, extensions) {
+            let items = from_file(&path);
+            result.push((path, items));
+        }
+    }
+    Ok(())
+}
+
+fn has_extension(path: &Path, extensions: &[&str]) -> bool {
+    path.extension()
+        .and_then(|extension| extension.to_str())
+        .is_some_and(|extension| extensions.iter().any(|x| x.eq_ignore_ascii_case(extension)))
 }
 
 /// Read subtitles from a buffered reader
 pub fn from_reader(reader: impl BufRead) -> Result<Vec<Item>, ReaderError> {
-    let parser = Parser::new(reader);
-    let mut result = Vec::new();
-    for item in parser {
-        let item = item?;
-        result.push(item);
+    from_reader_with(reader, ParserOptions::default())
+}
+
+/// Read subtitles from a buffered reader using given parser options
+pub fn from_reader_with(reader: impl BufRead, options: ParserOptions) -> Result<Vec<Item>, ReaderError> {
+    read_items(reader, options, 0, usize::MAX)
+}
+
+/// Read at most `limit` subtitles from a buffered reader
+///
+/// Reading stops as soon as the limit is reached, so the rest of input is not read and not validated.
+pub fn from_reader_limited(reader: impl BufRead, limit: usize) -> Result<Vec<Item>, ReaderError> {
+    read_items(reader, ParserOptions::default(), 0, limit)
+}
+
+/// Read subtitles from a reader
+///
+/// The reader is wrapped into [`BufReader`], use [`from_reader`] when it is buffered already.
+pub fn from_read<R: Read>(reader: R) -> Result<Vec<Item>, ReaderError> {
+    from_reader(BufReader::new(reader))
+}
+
+/// Average size of a subtitle in bytes used to estimate number of subtitles in the input
+const ITEM_SIZE_ESTIMATE: usize = 60;
+
+fn estimate_capacity(input_size: usize) -> usize {
+    input_size / ITEM_SIZE_ESTIMATE
+}
+
+fn read_items(
+    reader: impl BufRead,
+    options: ParserOptions,
+    capacity: usize,
+    limit: usize,
+) -> Result<Vec<Item>, ReaderError> {
+    let mut parser = Parser::with_options(reader, options);
+    let mut result = Vec::with_capacity(capacity.min(limit));
+    while result.len() < limit {
+        match parser.next() {
+            Some(Ok(item)) => result.push(item),
+            Some(Err(err)) => return Err(ReaderError::parse(err, result.len(), parser.line_number())),
+            None => break,
+        }
     }
     Ok(result)
 }
 
+/// Read all subtitles from a buffered reader collecting parse errors instead of stopping at the first one
+///
+/// A malformed subtitle is skipped up to the next blank line, reading stops only on an I/O error.
+///
+/// ```
+/// use std::io::Cursor;
+///
+/// let data = "1\nbad time\n\n2\n00:00:01,000 --> 00:00:02,000\nHello\n";
+/// let (items, errors) = srtparse::collect_all(Cursor::new(data));
+/// assert_eq!(items.len(), 1);
+/// assert_eq!(errors.len(), 1);
+/// ```
+pub fn collect_all(reader: impl BufRead) -> (Vec<Item>, Vec<ParseError>) {
+    let mut parser = Parser::new(reader);
+    let mut items = Vec::new();
+    let mut errors = Vec::new();
+    loop {
+        match parser.next() {
+            Some(Ok(item)) => items.push(item),
+            Some(Err(err)) => {
+                let is_fatal = matches!(err, ParseError::ReadLine(_));
+                errors.push(err);
+                if is_fatal {
+                    break;
+                }
+                parser.recover();
+            }
+            None => break,
+        }
+    }
+    (items, errors)
+}
+
+/// Parse subtitles from a buffered reader lazily
+///
+/// Returned parser is an iterator over parsed items,
+/// use `.collect::<Result<Vec<_>, _>>()` to get all of them or stop at the first error.
+///
+/// ```
+/// use std::{io::Cursor, time::Duration};
+///
+/// let data = "1\n00:00:01,000 --> 00:00:01,500\nShort\n\n2\n00:00:02,000 --> 00:00:04,000\nLong\n";
+/// let long = srtparse::parse_iter(Cursor::new(data))
+///     .filter(|item| match item {
+///         Ok(item) => item.end_time.into_duration() - item.start_time.into_duration() > Duration::from_secs(1),
+///         Err(_) => true,
+///     })
+///     .collect::<Result<Vec<_>, _>>()
+///     .unwrap();
+/// assert_eq!(long.len(), 1);
+/// assert_eq!(long[0].text, "Long");
+/// ```
+pub fn parse_iter<B>(reader: B) -> Parser<B>
+where
+    B: BufRead,
+{
+    Parser::new(reader)
+}
+
+/// Folds subtitles from a buffered reader without collecting them
+///
+/// Stops at the first parse error.
+///
+/// ```
+/// use std::{io::Cursor, time::Duration};
+///
+/// let data = "1\n00:00:01,000 --> 00:00:01,500\nShort\n\n2\n00:00:02,000 --> 00:00:04,000\nLong\n";
+/// let total = srtparse::try_fold_items(Cursor::new(data), Duration::ZERO, |acc, item| acc + item.duration()).unwrap();
+/// assert_eq!(total, Duration::from_millis(2500));
+/// ```
+pub fn try_fold_items<B, F>(reader: impl BufRead, init: B, mut f: F) -> Result<B, ParseError>
+where
+    F: FnMut(B, Item) -> B,
+{
+    Parser::new(reader).try_fold(init, |acc, item| Ok(f(acc, item?)))
+}
+
+/// Returns the `n`th subtitle from a buffered reader counting from zero
+///
+/// Subtitles are parsed lazily and reading stops once the `n`th one is found,
+/// returns `None` when input contains fewer subtitles.
+///
+/// ```
+/// use std::io::Cursor;
+///
+/// let data = "1\n00:00:01,000 --> 00:00:02,000\nfirst\n\n2\n00:00:03,000 --> 00:00:04,000\nsecond\n";
+/// let item = srtparse::nth_item(Cursor::new(data), 1).unwrap().unwrap();
+/// assert_eq!(item.text, "second");
+/// assert!(srtparse::nth_item(Cursor::new(data), 2).unwrap().is_none());
+/// ```
+pub fn nth_item(reader: impl BufRead, n: usize) -> Result<Option<Item>, ParseError> {
+    for (idx, item) in Parser::new(reader).enumerate() {
+        let item = item?;
+        if idx == n {
+            return Ok(Some(item));
+        }
+    }
+    Ok(None)
+}
+
+/// Read subtitles from a buffered reader along with their byte ranges in the input
+///
+/// A range starts at the position line and ends after the last text line of a subtitle,
+/// line terminators and a UTF-8 BOM are not included.
+pub fn parse_with_spans(mut reader: impl BufRead) -> Result<Vec<(Item, Range<usize>)>, ReaderError> {
+    let mut machine = Machine::new(ParserOptions::default());
+    let mut result = Vec::new();
+    let mut buf = String::new();
+    let mut offset = 0;
+    // Byte ranges of lines which may still belong to an item, starting from line number `first_line`
+    let mut lines = Vec::new();
+    let mut first_line = 1;
+    loop {
+        let line = if machine.needs_line() {
+            buf.clear();
+            let size = reader
+                .read_line(&mut buf)
+                .map_err(|err| ReaderError::parse(ParseError::ReadLine(err), result.len(), machine.line_number()))?;
+            if size == 0 {
+                None
+            } else {
+                let line = buf.trim_end_matches(['\n', '\r']);
+                let bom = if line.starts_with(UTF8_BOM) { UTF8_BOM.len() } else { 0 };
+                lines.push(offset + bom..offset + line.len());
+                offset += size;
+                Some(String::from(line))
+            }
+        } else {
+            None
+        };
+        let step = machine
+            .step(line)
+            .map_err(|err| ReaderError::parse(err, result.len(), machine.line_number()))?;
+        match step {
+            Step::Continue => {}
+            Step::Item(item) => {
+                let item_lines = machine.item_lines();
+                let span = lines[item_lines.start() - first_line].start..lines[item_lines.end() - first_line].end;
+                result.push((item, span));
+                lines.drain(..item_lines.end() + 1 - first_line);
+                first_line = item_lines.end() + 1;
+            }
+            Step::Done => return Ok(result),
+        }
+    }
+}
+
+/// Read subtitles from a buffered reader stopping at the first malformed subtitle
+///
+/// Returns parsed subtitles along with an unparsed tail of the input, if any.
+/// The tail starts at the first line of a subtitle which failed to parse
+/// and contains the rest of the input with line terminators normalized to `\n`.
+/// Only errors of reading the input are returned.
+pub fn parse_with_tail(reader: impl BufRead) -> Result<(Vec<Item>, Option<String>), ReaderError> {
+    let mut lines = reader.lines();
+    let mut machine = Machine::new(ParserOptions::default());
+    let mut result = Vec::new();
+    // Lines which may still belong to an item, starting from line number `first_line`
+    let mut block = Vec::new();
+    let mut first_line = 1;
+    loop {
+        let line = if machine.needs_line() {
+            let line = lines
+                .next()
+                .transpose()
+                .map_err(|err| ReaderError::parse(ParseError::ReadLine(err), result.len(), machine.line_number()))?;
+            if let Some(line) = &line {
+                block.push(line.trim_end_matches('\r').to_owned());
+            }
+            line
+        } else {
+            None
+        };
+        match machine.step(line) {
+            Ok(Step::Continue) => {}
+            Ok(Step::Item(item)) => {
+                result.push(item);
+                let last_line = *machine.item_lines().end();
+                block.drain(..last_line + 1 - first_line);
+                first_line = last_line + 1;
+            }
+            Ok(Step::Done) => return Ok((result, None)),
+            Err(ParseError::ReadLine(err)) => {
+                return Err(ReaderError::parse(
+                    ParseError::ReadLine(err),
+                    result.len(),
+                    machine.line_number(),
+                ))
+            }
+            Err(_) => break,
+        }
+    }
+    block.drain(..machine.item_lines().start().saturating_sub(first_line));
+    for (idx, line) in lines.enumerate() {
+        let line = line
+            .map_err(|err| ReaderError::parse(ParseError::ReadLine(err), result.len(), machine.line_number() + idx))?;
+        block.push(line.trim_end_matches('\r').to_owned());
+    }
+    while block.last().is_some_and(|line| line.trim().is_empty()) {
+        block.pop();
+    }
+    let tail = block.iter().skip_while(|line| line.trim().is_empty());
+    let tail = tail.map(String::as_str).collect::<Vec<_>>().join("\n");
+    Ok((result, Some(tail).filter(|tail| !tail.is_empty())))
+}
+
+/// Read subtitles from an asynchronous buffered reader
+#[cfg(feature = "tokio")]
+pub async fn from_async_reader<R>(reader: R) -> Result<Vec<Item>, ReaderError>
+where
+    R: tokio::io::AsyncBufRead + Unpin,
+{
+    from_async_reader_with(reader, ParserOptions::default()).await
+}
+
+/// Read subtitles from an asynchronous buffered reader using given parser options
+#[cfg(feature = "tokio")]
+pub async fn from_async_reader_with<R>(reader: R, options: ParserOptions) -> Result<Vec<Item>, ReaderError>
+where
+    R: tokio::io::AsyncBufRead + Unpin,
+{
+    use tokio::io::AsyncBufReadExt;
+
+    let mut lines = reader.lines();
+    let mut machine = Machine::new(options);
+    let mut result = Vec::new();
+    loop {
+        let line = if machine.needs_line() {
+            lines
+                .next_line()
+                .await
+                .map_err(|err| ReaderError::parse(ParseError::ReadLine(err), result.len(), machine.line_number()))?
+        } else {
+            None
+        };
+        let step = machine
+            .step(line)
+            .map_err(|err| ReaderError::parse(err, result.len(), machine.line_number()))?;
+        match step {
+            Step::Continue => {}
+            Step::Item(item) => result.push(item),
+            Step::Done => return Ok(result),
+        }
+    }
+}
+
 /// An error when reading subtitles
 #[derive(Debug)]
 pub enum ReaderError {
     /// Could not open a file
     OpenFile(IoError),
+    /// Could not read a directory
+    ReadDir(IoError),
     /// Failed to parse subtitles
-    Parse(ParseError),
+    Parse {
+        /// Parser error
+        error: ParseError,
+        /// Number of subtitles parsed successfully before the failure
+        parsed: usize,
+        /// Number of line where parsing failed, starting from 1
+        line: usize,
+    },
 }
 
-impl From<ParseError> for ReaderError {
-    fn from(err: ParseError) -> Self {
-        ReaderError::Parse(err)
+impl ReaderError {
+    fn parse(error: ParseError, parsed: usize, line_number: usize) -> Self {
+        let line = match error {
+            ParseError::ReadLine(_) => line_number + 1,
+            _ => line_number,
+        };
+        ReaderError::Parse { error, parsed, line }
     }
 }
 
@@ -54,7 +459,10 @@ impl fmt::Display for ReaderError {
         use self::ReaderError::*;
         match self {
             OpenFile(err) => write!(out, "could not open a file: {err}"),
-            Parse(err) => write!(out, "parse error: {err}"),
+            ReadDir(err) => write!(out, "could not read a directory: {err}"),
+            Parse { error, parsed, line } => {
+                write!(out, "parse error at line {line} after {parsed} subtitles: {error}")
+            }
         }
     }
 }
@@ -64,7 +472,8 @@ impl Error for ReaderError {
         use self::ReaderError::*;
         match self {
             OpenFile(err) => Some(err),
-            Parse(err) => Some(err),
+            ReadDir(err) => Some(err),
+            Parse { error, .. } => Some(error),
         }
     }
 }
@@ -74,12 +483,102 @@ mod tests {
     use super::*;
     use std::time::Duration;
 
+    #[test]
+    #[allow(deprecated)]
+    fn read_subtitles() {
+        fn first_text(subtitles: &[crate::Subtitle]) -> &str {
+            &subtitles[0].text
+        }
+        let items = from_str("1\n00:00:01,100 --> 00:00:02,120\nHello!").unwrap();
+        assert_eq!(first_text(&items), "Hello!");
+    }
+
+    #[cfg(feature = "tokio")]
+    #[tokio::test]
+    async fn read_from_async_reader() {
+        let data = std::fs::read("./data/underworld.srt").unwrap();
+        let result = from_async_reader(tokio::io::BufReader::new(Cursor::new(data)))
+            .await
+            .unwrap();
+        assert_eq!(result, from_file("./data/underworld.srt").unwrap());
+
+        let err = from_async_reader(tokio::io::BufReader::new(Cursor::new("1\nbad time")))
+            .await
+            .unwrap_err()
+            .to_string();
+        assert_eq!(
+            err,
+            "parse error at line 2 after 0 subtitles: failed to parse start time: could not parse hours: invalid digit found in string"
+        );
+    }
+
     #[test]
     fn read_from_file_failed() {
         let err = from_file("/file/does/not/exist").unwrap_err().to_string();
         assert_eq!(err, "could not open a file: No such file or directory (os error 2)");
     }
 
+    #[test]
+    fn read_from_dir() {
+        let dir = std::env::temp_dir().join(format!("srtparse-from-dir-{}", std::process::id()));
+        std::fs::create_dir_all(dir.join("nested")).unwrap();
+        std::fs::copy("./data/underworld.srt", dir.join("good.srt")).unwrap();
+        std::fs::write(dir.join("bad.SRT"), "1\nbad time\n").unwrap();
+        std::fs::write(dir.join("notes.txt"), "not subtitles").unwrap();
+        std::fs::copy("./data/underworld.srt", dir.join("nested/skipped.srt")).unwrap();
+
+        let result = from_dir(&dir);
+        std::fs::remove_dir_all(&dir).unwrap();
+        let result = result.unwrap();
+        assert_eq!(result.len(), 2);
+        assert_eq!(result[0].0, dir.join("bad.SRT"));
+        assert!(matches!(
+            result[0].1,
+            Err(ReaderError::Parse { parsed: 0, line: 2, .. })
+        ));
+        assert_eq!(result[1].0, dir.join("good.srt"));
+        assert_eq!(result[1].1.as_ref().unwrap().len(), 706);
+
+        assert!(matches!(from_dir(&dir), Err(ReaderError::ReadDir(_))));
+    }
+
+    #[test]
+    fn read_from_dir_recursive() {
+        let dir = std::env::temp_dir().join(format!("srtparse-from-dir-recursive-{}", std::process::id()));
+        std::fs::create_dir_all(dir.join("a/b")).unwrap();
+        std::fs::create_dir_all(dir.join("c")).unwrap();
+        std::fs::copy("./data/underworld.srt", dir.join("root.srt")).unwrap();
+        std::fs::write(dir.join("a/first.SRT"), "1\n00:00:01,000 --> 00:00:02,000\nfirst\n").unwrap();
+        std::fs::write(dir.join("a/b/second.sub"), "1\n00:00:01,000 --> 00:00:02,000\nsecond\n").unwrap();
+        std::fs::write(dir.join("a/b/bad.srt"), "1\nbad time\n").unwrap();
+        std::fs::write(dir.join("c/notes.txt"), "not subtitles").unwrap();
+
+        let result = from_dir_recursive(&dir, &["srt", "sub"]);
+        let srt_only = from_dir_recursive(&dir, &["srt"]);
+        std::fs::remove_dir_all(&dir).unwrap();
+        let result = result.unwrap();
+        let paths = result.iter().map(|(path, _)| path.clone()).collect::<Vec<_>>();
+        assert_eq!(
+            paths,
+            vec![
+                dir.join("a/b/bad.srt"),
+                dir.join("a/b/second.sub"),
+                dir.join("a/first.SRT"),
+                dir.join("root.srt"),
+            ]
+        );
+        assert!(matches!(result[0].1, Err(ReaderError::Parse { .. })));
+        assert_eq!(result[1].1.as_ref().unwrap()[0].text, "second");
+        assert_eq!(result[2].1.as_ref().unwrap()[0].text, "first");
+        assert_eq!(result[3].1.as_ref().unwrap().len(), 706);
+        assert_eq!(srt_only.unwrap().len(), 3);
+
+        assert!(matches!(
+            from_dir_recursive(&dir, &["srt"]),
+            Err(ReaderError::ReadDir(_))
+        ));
+    }
+
     #[test]
     fn read_from_file_success() {
         let result = from_file("./data/underworld.srt").unwrap();
@@ -97,4 +596,205 @@ mod tests {
         assert_eq!(last.end_time.into_duration(), Duration::from_millis(6_804_381));
         assert_eq!(last.text, "... будет объявлена охота.");
     }
+
+    #[test]
+    fn read_with_spans_without_blank_lines() {
+        let data = "1\n00:00:01,000 --> 00:00:02,000\nok\n2\n00:00:03,000 --> 00:00:04,000\nx\n\n";
+        let result = parse_with_spans(Cursor::new(data)).unwrap();
+        assert_eq!(result.len(), 2);
+        assert_eq!(&data[result[0].1.clone()], "1\n00:00:01,000 --> 00:00:02,000\nok");
+        assert_eq!(&data[result[1].1.clone()], "2\n00:00:03,000 --> 00:00:04,000\nx");
+
+        let data = "1\n00:00:01,000 --> 00:00:02,000\n42\nanswer\n";
+        let result = parse_with_spans(Cursor::new(data)).unwrap();
+        assert_eq!(&data[result[0].1.clone()], data.trim_end());
+    }
+
+    #[test]
+    fn read_corrupted() {
+        let data = std::fs::read_to_string("./data/underworld.srt").unwrap();
+        let mut lines = data.lines().collect::<Vec<_>>();
+        assert_eq!(lines[1091], "246");
+        lines[1092] = "00:20:00,000 --> bad end time";
+        let data = lines.join("\n");
+        match from_str(&data).unwrap_err() {
+            ReaderError::Parse { error, parsed, line } => {
+                assert!(matches!(error, ParseError::ParseTimeEnd(_)));
+                assert_eq!(parsed, 245);
+                assert_eq!(line, 1093);
+            }
+            err => panic!("unexpected error: {err}"),
+        }
+        match parse_with_spans(Cursor::new(&data)).unwrap_err() {
+            ReaderError::Parse { parsed, line, .. } => assert_eq!((parsed, line), (245, 1093)),
+            err => panic!("unexpected error: {err}"),
+        }
+    }
+
+    #[cfg(feature = "gzip")]
+    #[test]
+    fn read_from_gz_file() {
+        let result = from_gz_file("./data/underworld.srt.gz").unwrap();
+        assert_eq!(result, from_file("./data/underworld.srt").unwrap());
+
+        let err = from_gz_file("./data/underworld.srt").unwrap_err();
+        assert!(matches!(
+            err,
+            ReaderError::Parse {
+                error: ParseError::ReadLine(_),
+                parsed: 0,
+                line: 1
+            }
+        ));
+    }
+
+    #[test]
+    fn read_with_estimated_capacity() {
+        let data = std::fs::read("./data/underworld.srt").unwrap();
+        let expected = parse_iter(Cursor::new(&data)).collect::<Result<Vec<_>, _>>().unwrap();
+        let result = from_str(&data).unwrap();
+        assert!(result.capacity() >= estimate_capacity(data.len()));
+        assert_eq!(result, expected);
+        let result = from_file("./data/underworld.srt").unwrap();
+        assert!(result.capacity() >= estimate_capacity(data.len()));
+        assert_eq!(result, expected);
+        assert_eq!(from_reader(Cursor::new(&data)).unwrap(), expected);
+    }
+
+    #[test]
+    fn read_from_read() {
+        struct Unbuffered<'a>(&'a [u8]);
+
+        impl Read for Unbuffered<'_> {
+            fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+                self.0.read(buf)
+            }
+        }
+
+        let data = "1\n00:00:01,000 --> 00:00:02,000\nHello\n\n2\n00:00:03,000 --> 00:00:04,000\nWorld\n";
+        let result = from_read(Unbuffered(data.as_bytes())).unwrap();
+        assert_eq!(result, from_str(data).unwrap());
+    }
+
+    #[test]
+    fn read_with_tail() {
+        let data = "1\n00:00:01,000 --> 00:00:02,000\nfirst\n\n2\n00:00:03,000 --> 00:00:04,000\nsecond\n\n";
+        let (items, tail) = parse_with_tail(Cursor::new(data)).unwrap();
+        assert_eq!(items, from_str(data).unwrap());
+        assert_eq!(tail, None);
+
+        let (items, tail) = parse_with_tail(Cursor::new(format!("{data}garbage\r\nmore garbage\n\n"))).unwrap();
+        assert_eq!(items, from_str(data).unwrap());
+        assert_eq!(tail.as_deref(), Some("garbage\nmore garbage"));
+
+        let (items, tail) = parse_with_tail(Cursor::new(format!("{data}\n3\n00:00:05,000 --> bad\nthird"))).unwrap();
+        assert_eq!(items.len(), 2);
+        assert_eq!(tail.as_deref(), Some("3\n00:00:05,000 --> bad\nthird"));
+
+        let (items, tail) = parse_with_tail(Cursor::new(format!("{data}3\n"))).unwrap();
+        assert_eq!(items.len(), 2);
+        assert_eq!(tail.as_deref(), Some("3"));
+
+        let data = "1\n00:00:01,000 --> 00:00:02,000\nok\n2\n00:00:03,000 --> bad\nx";
+        let (items, tail) = parse_with_tail(Cursor::new(data)).unwrap();
+        assert_eq!(items.len(), 1);
+        assert_eq!(items[0].text, "ok");
+        assert_eq!(tail.as_deref(), Some("2\n00:00:03,000 --> bad\nx"));
+    }
+
+    #[test]
+    fn fold_items() {
+        let reader = BufReader::new(File::open("./data/underworld.srt").unwrap());
+        let total = try_fold_items(reader, Duration::ZERO, |acc, item| acc + item.duration()).unwrap();
+        let items = from_file("./data/underworld.srt").unwrap();
+        assert_eq!(total, items.iter().map(Item::duration).sum());
+
+        let err = try_fold_items(Cursor::new("1\nbad time"), 0, |acc, _| acc + 1).unwrap_err();
+        assert!(matches!(err, ParseError::ParseTimeStart(_)));
+    }
+
+    #[test]
+    fn read_limited() {
+        let reader = BufReader::new(File::open("./data/underworld.srt").unwrap());
+        let result = from_reader_limited(reader, 2).unwrap();
+        let expected = from_file("./data/underworld.srt").unwrap();
+        assert_eq!(result, expected[..2]);
+
+        let data = "1\n00:00:01,000 --> 00:00:02,000\nfirst\n\n2\nbad time\n";
+        assert_eq!(from_reader_limited(Cursor::new(data), 1).unwrap().len(), 1);
+        assert!(from_reader_limited(Cursor::new(data), 2).is_err());
+        assert!(from_reader_limited(Cursor::new(data), 0).unwrap().is_empty());
+    }
+
+    #[test]
+    fn read_collect_all() {
+        let data = "1\n00:00:01,000 --> 00:00:02,000\nfirst\n\n2\n00:00:03,000 --> bad\nbroken\ntext\n\n\
+                    3\n00:00:05,000 --> 00:00:06,000\nthird\n\nfour\n00:00:07,000 --> 00:00:08,000\nfourth\n\n\
+                    5\n00:00:09,000 --> 00:00:10,000\nfifth";
+        let (items, errors) = collect_all(Cursor::new(data));
+        assert_eq!(
+            items.iter().map(|item| item.text.as_str()).collect::<Vec<_>>(),
+            ["first", "third", "fifth"]
+        );
+        assert_eq!(errors.len(), 2);
+        assert!(matches!(errors[0], ParseError::ParseTimeEnd(_)));
+        assert!(matches!(errors[1], ParseError::BadPosition(_)));
+
+        let (items, errors) = collect_all(Cursor::new("1\n00:00:01,000 --> 00:00:02,000\nok\n\n2\nbad\n\n"));
+        assert_eq!(items.len(), 1);
+        assert_eq!(errors.len(), 1);
+        assert!(matches!(errors[0], ParseError::ParseTimeStart(_)));
+
+        let reader = BufReader::new(File::open("./data/underworld.srt").unwrap());
+        let (items, errors) = collect_all(reader);
+        assert_eq!(items.len(), 706);
+        assert!(errors.is_empty());
+    }
+
+    #[test]
+    fn read_iter_str() {
+        let data = std::fs::read_to_string("./data/underworld.srt").unwrap();
+        let items = iter_str(&data).collect::<Result<Vec<_>, _>>().unwrap();
+        assert_eq!(items, from_str(&data).unwrap());
+        assert!(iter_str("").next().is_none());
+    }
+
+    #[test]
+    fn read_nth_item() {
+        let reader = BufReader::new(File::open("./data/underworld.srt").unwrap());
+        let item = nth_item(reader, 2).unwrap().unwrap();
+        let items = from_file("./data/underworld.srt").unwrap();
+        assert_eq!(item, items[2]);
+
+        let data = "1\n00:00:01,000 --> 00:00:02,000\nfirst\n\n2\nbad time\n";
+        assert_eq!(nth_item(Cursor::new(data), 0).unwrap().unwrap().text, "first");
+        assert!(nth_item(Cursor::new(data), 1).is_err());
+        assert!(nth_item(Cursor::new(""), 0).unwrap().is_none());
+    }
+
+    #[test]
+    fn read_with_spans() {
+        let data = "\u{feff}1\r\n00:00:01,000 --> 00:00:02,000\r\nfirst\r\n\r\n2\r\n00:00:03,000 --> 00:00:04,000\r\nsecond\r\nline\r\n\r\n3\r\n00:00:05,000 --> 00:00:06,000\r\nthird";
+        let result = parse_with_spans(Cursor::new(data)).unwrap();
+        assert_eq!(result.len(), 3);
+        let (item, span) = &result[1];
+        assert_eq!(item.pos, 2);
+        assert_eq!(span, &(46..92));
+        assert_eq!(
+            &data[span.clone()],
+            "2\r\n00:00:03,000 --> 00:00:04,000\r\nsecond\r\nline"
+        );
+        assert_eq!(
+            &data[result[0].1.clone()],
+            "1\r\n00:00:01,000 --> 00:00:02,000\r\nfirst"
+        );
+        assert_eq!(
+            &data[result[2].1.clone()],
+            "3\r\n00:00:05,000 --> 00:00:06,000\r\nthird"
+        );
+        assert_eq!(
+            result.into_iter().map(|(item, _)| item).collect::<Vec<_>>(),
+            from_str(data).unwrap()
+        );
+    }
 }
@@ -0,0 +1,48 @@
+use alloc::string::String;
+
+/// Rewrites dot millisecond separators to commas in time lines
+///
+/// A line is considered to be a time line when it contains `-->`,
+/// only start and end times are rewritten, so text lines and coordinates after the end time are left untouched.
+pub fn normalize_separators(input: &str) -> String {
+    let mut result = String::with_capacity(input.len());
+    for line in input.split_inclusive('\n') {
+        let Some((start, rest)) = line.split_once("-->") else {
+            result.push_str(line);
+            continue;
+        };
+        let end = rest.trim_start();
+        let (end, tail) = end.split_at(end.find(char::is_whitespace).unwrap_or(end.len()));
+        result.push_str(&start.replace('.', ","));
+        result.push_str("-->");
+        result.push_str(&rest[..rest.len() - end.len() - tail.len()]);
+        result.push_str(&end.replace('.', ","));
+        result.push_str(tail);
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn separators() {
+        let input =
+            "1\r\n00:00:01.100 --> 00:00:02.200\r\nIt costs 1.5 dollars...\r\n\r\n2\n00:00:03,000 --> 00:00:04.5\nOK.";
+        assert_eq!(
+            normalize_separators(input),
+            "1\r\n00:00:01,100 --> 00:00:02,200\r\nIt costs 1.5 dollars...\r\n\r\n2\n00:00:03,000 --> 00:00:04,5\nOK."
+        );
+        assert_eq!(normalize_separators(""), "");
+    }
+
+    #[test]
+    fn separators_with_coordinates() {
+        let input = "1\n00:00:01.100 --> 00:00:02.200 X1:1.5 X2:2 Y1:1 Y2:2\ntext\n";
+        assert_eq!(
+            normalize_separators(input),
+            "1\n00:00:01,100 --> 00:00:02,200 X1:1.5 X2:2 Y1:1 Y2:2\ntext\n"
+        );
+    }
+}
@@ -0,0 +1,103 @@
+use crate::{item::Item, time::Time};
+use alloc::{string::String, vec::Vec};
+
+/// Returns the first subtitle displayed at the given time
+///
+/// A subtitle is displayed within `start_time..end_time` range.
+/// Subtitles must be sorted by start time.
+pub fn active_at(items: &[Item], t: Time) -> Option<&Item> {
+    let started = items.partition_point(|item| item.start_time <= t);
+    items[..started].iter().find(|item| t < item.end_time)
+}
+
+/// Returns subtitles containing the given query
+///
+/// Search is case-insensitive and treats any sequence of whitespaces (including line breaks) as a single space,
+/// so a query may span multiple lines of a subtitle.
+/// An empty or whitespace-only query matches nothing.
+pub fn search<'a>(items: &'a [Item], query: &str) -> Vec<&'a Item> {
+    let query = normalize(query);
+    if query.is_empty() {
+        return Vec::new();
+    }
+    items
+        .iter()
+        .filter(|item| normalize(&item.text).contains(&query))
+        .collect()
+}
+
+fn normalize(value: &str) -> String {
+    let mut result = String::with_capacity(value.len());
+    for word in value.split_whitespace() {
+        if !result.is_empty() {
+            result.push(' ');
+        }
+        result.extend(word.chars().flat_map(char::to_lowercase));
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn time(seconds: u64) -> Time {
+        Time {
+            hours: 0,
+            minutes: 0,
+            seconds,
+            milliseconds: 0,
+        }
+    }
+
+    fn item(pos: usize, start: u64, end: u64) -> Item {
+        item_with_text(pos, start, end, &format!("text {pos}"))
+    }
+
+    fn item_with_text(pos: usize, start: u64, end: u64, text: &str) -> Item {
+        Item {
+            pos,
+            start_time: time(start),
+            end_time: time(end),
+            text: String::from(text),
+            label: None,
+            coordinates: None,
+        }
+    }
+
+    #[test]
+    fn active() {
+        let items = vec![item(1, 1, 3), item(2, 5, 8), item(3, 7, 9)];
+        assert_eq!(active_at(&items, time(2)).map(|x| x.pos), Some(1));
+        assert_eq!(active_at(&items, time(4)), None);
+        assert_eq!(active_at(&items, time(0)), None);
+        assert_eq!(active_at(&items, time(10)), None);
+        assert_eq!(active_at(&items, time(1)).map(|x| x.pos), Some(1));
+        assert_eq!(active_at(&items, time(3)), None);
+        assert_eq!(active_at(&items, time(7)).map(|x| x.pos), Some(2));
+        assert_eq!(active_at(&items, time(8)).map(|x| x.pos), Some(3));
+        assert_eq!(active_at(&[], time(8)), None);
+    }
+
+    #[test]
+    fn search_text() {
+        let items = vec![
+            item_with_text(1, 1, 2, "The war had all but ground to a halt\nin the blink of an eye."),
+            item_with_text(2, 3, 4, "Lucian, the most feared"),
+            item_with_text(3, 5, 6, "ВОЙНА закончилась"),
+        ];
+        let found = |query| search(&items, query).iter().map(|x| x.pos).collect::<Vec<_>>();
+        assert_eq!(found("halt in the"), vec![1]);
+        assert_eq!(found("LUCIAN"), vec![2]);
+        assert_eq!(found("the"), vec![1, 2]);
+        assert_eq!(found("война"), vec![3]);
+        assert!(found("vampire").is_empty());
+    }
+
+    #[test]
+    fn search_empty_query() {
+        let items = vec![item(1, 1, 2), item(2, 3, 4)];
+        assert!(search(&items, "").is_empty());
+        assert!(search(&items, " \n\t").is_empty());
+    }
+}
@@ -0,0 +1,230 @@
+use crate::{item::Item, time::Time};
+use alloc::{vec, vec::Vec};
+use core::{slice::Windows, time::Duration};
+
+/// Counts gaps between consecutive subtitles in buckets
+///
+/// `buckets` contains ascending bucket boundaries, so the result has `buckets.len() + 1` counters:
+/// first one counts gaps shorter than `buckets[0]`,
+/// the `i`-th one counts gaps in range `buckets[i - 1]..buckets[i]`,
+/// and the last one counts gaps greater than or equal to the last boundary.
+///
+/// Overlapping subtitles are considered to have a zero gap.
+pub fn gap_histogram(items: &[Item], buckets: &[Duration]) -> Vec<usize> {
+    let mut result = vec![0; buckets.len() + 1];
+    for (_, gap) in gaps(items) {
+        result[buckets.partition_point(|boundary| *boundary <= gap)] += 1;
+    }
+    result
+}
+
+/// Returns gaps between consecutive subtitles
+///
+/// Each entry contains an index of a subtitle and a gap between it and the next one.
+/// Overlapping subtitles are considered to have a zero gap.
+pub fn gaps(items: &[Item]) -> Vec<(usize, Duration)> {
+    GapIter::new(items).enumerate().collect()
+}
+
+/// Returns an iterator over pairs of consecutive subtitles
+pub fn windows_pairs(items: &[Item]) -> impl Iterator<Item = (&Item, &Item)> {
+    items.windows(2).map(|pair| (&pair[0], &pair[1]))
+}
+
+/// An iterator over gaps between consecutive subtitles
+///
+/// Yields a gap after each subtitle except the last one.
+/// Overlapping subtitles are considered to have a zero gap.
+#[derive(Clone, Debug)]
+pub struct GapIter<'a> {
+    pairs: Windows<'a, Item>,
+}
+
+impl<'a> GapIter<'a> {
+    /// Creates an iterator over gaps between given subtitles
+    pub fn new(items: &'a [Item]) -> Self {
+        Self {
+            pairs: items.windows(2),
+        }
+    }
+}
+
+impl Iterator for GapIter<'_> {
+    type Item = Duration;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.pairs.next().map(|pair| gap(&pair[0], &pair[1]))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.pairs.size_hint()
+    }
+}
+
+impl ExactSizeIterator for GapIter<'_> {}
+
+/// An overview of subtitles, see [`summary`]
+#[derive(Clone, Debug, PartialEq)]
+pub struct Summary {
+    /// Number of subtitles
+    pub count: usize,
+    /// Sum of display durations of all subtitles
+    pub total_display: Duration,
+    /// The earliest start time
+    pub first_start: Option<Time>,
+    /// The latest end time
+    pub last_end: Option<Time>,
+    /// Average reading speed in characters per second
+    ///
+    /// Computed as a total number of characters in [`Item::plain_text`] excluding line breaks
+    /// divided by [`Summary::total_display`], zero when the latter is zero.
+    pub avg_cps: f64,
+}
+
+/// Returns an overview of subtitles
+pub fn summary(items: &[Item]) -> Summary {
+    let total_display = items.iter().map(Item::duration).sum::<Duration>();
+    let chars = items.iter().map(|item| item.char_count(true, true)).sum::<usize>();
+    Summary {
+        count: items.len(),
+        total_display,
+        first_start: items
+            .iter()
+            .map(|item| item.start_time)
+            .min_by_key(|time| time.into_duration()),
+        last_end: items
+            .iter()
+            .map(|item| item.end_time)
+            .max_by_key(|time| time.into_duration()),
+        avg_cps: if total_display.is_zero() {
+            0.0
+        } else {
+            chars as f64 / total_display.as_secs_f64()
+        },
+    }
+}
+
+pub(crate) fn gap(item: &Item, next: &Item) -> Duration {
+    next.start_time
+        .into_duration()
+        .saturating_sub(item.end_time.into_duration())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn time(milliseconds: u64) -> Time {
+        Time {
+            hours: 0,
+            minutes: 0,
+            seconds: milliseconds / 1000,
+            milliseconds: milliseconds % 1000,
+        }
+    }
+
+    fn item(pos: usize, start: u64, end: u64) -> Item {
+        Item {
+            pos,
+            start_time: time(start),
+            end_time: time(end),
+            text: String::from("text"),
+            label: None,
+            coordinates: None,
+        }
+    }
+
+    #[test]
+    fn histogram() {
+        let items = vec![
+            item(1, 0, 1000),
+            item(2, 1000, 2000),  // 0ms gap
+            item(3, 2100, 3000),  // 100ms gap
+            item(4, 3500, 4000),  // 500ms gap
+            item(5, 5000, 6000),  // 1000ms gap
+            item(6, 9000, 10000), // 3000ms gap
+            item(7, 9500, 11000), // overlap
+        ];
+        let buckets = [
+            Duration::from_millis(100),
+            Duration::from_millis(1000),
+            Duration::from_millis(2000),
+        ];
+        assert_eq!(gap_histogram(&items, &buckets), vec![2, 2, 1, 1]);
+        assert_eq!(gap_histogram(&items, &[]), vec![6]);
+        assert_eq!(gap_histogram(&[], &buckets), vec![0, 0, 0, 0]);
+    }
+
+    #[test]
+    fn gaps_between_items() {
+        let items = vec![item(1, 0, 1000), item(2, 1500, 2000), item(3, 1800, 3000)];
+        assert_eq!(gaps(&items), vec![(0, Duration::from_millis(500)), (1, Duration::ZERO)]);
+        assert!(gaps(&items[..1]).is_empty());
+    }
+
+    #[test]
+    fn gap_iter() {
+        let items = vec![item(1, 0, 1000), item(2, 1500, 2000), item(3, 1800, 3000)];
+        assert_eq!(
+            GapIter::new(&items).collect::<Vec<_>>(),
+            [Duration::from_millis(500), Duration::ZERO]
+        );
+        assert_eq!(GapIter::new(&items).len(), 2);
+        assert_eq!(GapIter::new(&[]).next(), None);
+        let pairs = windows_pairs(&items).map(|(a, b)| (a.pos, b.pos)).collect::<Vec<_>>();
+        assert_eq!(pairs, [(1, 2), (2, 3)]);
+    }
+
+    #[test]
+    fn summary_of_fixture() {
+        let items = crate::reader::from_file("./data/underworld.srt").unwrap();
+        let result = summary(&items);
+        assert_eq!(result.count, 706);
+        assert_eq!(result.first_start, Some(items[0].start_time));
+        assert_eq!(result.last_end, Some(items[705].end_time));
+        assert_eq!(result.total_display, Duration::from_millis(1_674_348));
+        assert!(result.avg_cps > 5.0 && result.avg_cps < 25.0);
+    }
+
+    #[test]
+    fn summary_of_items() {
+        let items = vec![item(1, 1000, 3000), item(2, 500, 1000)];
+        assert_eq!(
+            summary(&items),
+            Summary {
+                count: 2,
+                total_display: Duration::from_millis(2500),
+                first_start: Some(time(500)),
+                last_end: Some(time(3000)),
+                avg_cps: 3.2,
+            }
+        );
+        assert_eq!(
+            summary(&[]),
+            Summary {
+                count: 0,
+                total_display: Duration::ZERO,
+                first_start: None,
+                last_end: None,
+                avg_cps: 0.0,
+            }
+        );
+    }
+
+    #[test]
+    fn summary_of_unnormalized_times() {
+        let unnormalized = |milliseconds| Time {
+            hours: 0,
+            minutes: 0,
+            seconds: 0,
+            milliseconds,
+        };
+        let mut first = item(1, 1000, 3000);
+        first.end_time = unnormalized(5000);
+        let mut second = item(2, 1000, 3000);
+        second.start_time = unnormalized(1500);
+        let result = summary(&[first, second]);
+        assert_eq!(result.first_start, Some(time(1000)));
+        assert_eq!(result.last_end, Some(unnormalized(5000)));
+    }
+}
@@ -0,0 +1,112 @@
+use crate::{
+    edit::{renumber, shift},
+    item::Item,
+    validate::{validate, ValidationIssue},
+    writer::to_string,
+};
+use alloc::{string::String, vec::Vec};
+use core::ops::{Deref, DerefMut};
+
+/// A list of subtitles
+///
+/// Wraps `Vec<Item>` and provides chainable methods to edit subtitles.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Subtitles(pub Vec<Item>);
+
+impl Subtitles {
+    /// Shifts subtitles by the given number of milliseconds, see [`shift`](crate::shift)
+    pub fn shift(&mut self, offset: i64) -> &mut Self {
+        shift(&mut self.0, offset);
+        self
+    }
+
+    /// Assigns sequential positions to subtitles, see [`renumber`](crate::renumber)
+    pub fn renumber(&mut self) -> &mut Self {
+        renumber(&mut self.0);
+        self
+    }
+
+    /// Checks subtitles and returns all found problems, see [`validate`](crate::validate)
+    pub fn validate(&self) -> Vec<ValidationIssue> {
+        validate(&self.0)
+    }
+
+    /// Writes subtitles to a string, see [`to_string`](crate::to_string)
+    pub fn to_srt_string(&self) -> String {
+        to_string(&self.0)
+    }
+
+    /// Returns inner list of subtitles
+    pub fn into_inner(self) -> Vec<Item> {
+        self.0
+    }
+}
+
+impl Deref for Subtitles {
+    type Target = [Item];
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl DerefMut for Subtitles {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+impl From<Vec<Item>> for Subtitles {
+    fn from(items: Vec<Item>) -> Self {
+        Subtitles(items)
+    }
+}
+
+impl From<Subtitles> for Vec<Item> {
+    fn from(subtitles: Subtitles) -> Self {
+        subtitles.0
+    }
+}
+
+impl FromIterator<Item> for Subtitles {
+    fn from_iter<I: IntoIterator<Item = Item>>(iter: I) -> Self {
+        Subtitles(iter.into_iter().collect())
+    }
+}
+
+impl IntoIterator for Subtitles {
+    type Item = Item;
+    type IntoIter = alloc::vec::IntoIter<Item>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.into_iter()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::reader::{from_file, from_str};
+    use std::time::Duration;
+
+    #[test]
+    fn fluent() {
+        let mut subtitles = Subtitles::from(from_file("./data/underworld.srt").unwrap());
+        assert_eq!(subtitles.len(), 706);
+        assert!(subtitles.validate().is_empty());
+        subtitles.shift(-1000).renumber();
+        assert_eq!(subtitles[0].start_time.into_duration(), Duration::from_millis(57392));
+        assert_eq!(subtitles.last().unwrap().pos, 706);
+    }
+
+    #[test]
+    fn srt_string() {
+        let source = "2\n00:00:01,000-->00:00:02,000\nHello\n";
+        let mut subtitles: Subtitles = from_str(source).unwrap().into();
+        assert_eq!(subtitles.to_srt_string(), source);
+        assert_eq!(
+            subtitles.shift(500).renumber().to_srt_string(),
+            "1\n00:00:01,500-->00:00:02,500\nHello\n"
+        );
+    }
+}
@@ -1,7 +1,17 @@
-use std::{error::Error, fmt, num::ParseIntError, str::FromStr, time::Duration};
+use alloc::{format, string::String};
+use core::{
+    error::Error,
+    fmt, iter,
+    num::ParseIntError,
+    ops::{Add, Sub},
+    str::FromStr,
+    time::Duration,
+};
 
 /// Describes the time when subtitle should appear or disappear
-#[derive(Clone, Copy, Debug, PartialEq)]
+///
+/// Ordering compares fields from hours to milliseconds, so it is consistent with durations for normalized times only.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
 pub struct Time {
     /// Number of hours
     pub hours: u64,
@@ -14,12 +24,123 @@ pub struct Time {
 }
 
 impl Time {
+    /// Creates `Time` from `Duration` from standard library
+    ///
+    /// Sub-millisecond precision is truncated.
+    pub fn from_duration(duration: Duration) -> Self {
+        let milliseconds = duration.as_millis() as u64;
+        let seconds = milliseconds / 1000;
+        let minutes = seconds / 60;
+        Self {
+            hours: minutes / 60,
+            minutes: minutes % 60,
+            seconds: seconds % 60,
+            milliseconds: milliseconds % 1000,
+        }
+    }
+
+    /// Parses time allowing hours to be omitted
+    ///
+    /// Unlike `FromStr` implementation, accepts `MM:SS,mmm` along with `HH:MM:SS,mmm`.
+    pub fn parse_flexible(raw: &str) -> Result<Self, ParseTimeError> {
+        let raw = raw.trim();
+        let time = raw.split(',').next().unwrap_or_default();
+        if time.matches(':').count() == 1 && !raw.starts_with('-') {
+            format!("0:{raw}").parse()
+        } else {
+            raw.parse()
+        }
+    }
+
+    /// Returns time with overflowing fields carried into larger ones
+    ///
+    /// For example, 1500 milliseconds become 1 second and 500 milliseconds,
+    /// 90 seconds become 1 minute and 30 seconds.
+    pub fn normalized(self) -> Self {
+        Self::from_duration(self.into_duration())
+    }
+
+    /// Creates `Time` from a frame number at the given frame rate
+    ///
+    /// The result is rounded to the nearest millisecond,
+    /// so for fractional rates like 23.976 or 29.97 it may differ from the exact frame time by less than 1ms.
+    /// Drop-frame timecode is not taken into account, frames are counted continuously.
+    ///
+    /// `fps` must be positive and finite, otherwise the result is meaningless.
+    pub fn from_frames(frame: u64, fps: f64) -> Self {
+        debug_assert!(fps > 0.0 && fps.is_finite(), "frame rate must be positive and finite");
+        Self::from_duration(Duration::from_millis(round(frame as f64 * 1000.0 / fps)))
+    }
+
+    /// Returns a frame number at the given frame rate
+    ///
+    /// The result is rounded to the nearest frame, so `Time::from_frames(n, fps).to_frames(fps)` is `n`
+    /// for common frame rates.
+    ///
+    /// `fps` must be positive and finite, otherwise the result is meaningless.
+    pub fn to_frames(&self, fps: f64) -> u64 {
+        debug_assert!(fps > 0.0 && fps.is_finite(), "frame rate must be positive and finite");
+        round(self.total_milliseconds() as f64 * fps / 1000.0)
+    }
+
     /// Converts `Time` to `Duration` from standard library
     pub fn into_duration(self) -> Duration {
+        Duration::from_millis(self.total_milliseconds())
+    }
+
+    /// Returns total number of milliseconds
+    pub fn total_milliseconds(&self) -> u64 {
         let minutes = self.minutes + (self.hours * 60);
         let seconds = self.seconds + (minutes * 60);
-        let milliseconds = self.milliseconds + (seconds * 1000);
-        Duration::from_millis(milliseconds)
+        self.milliseconds + (seconds * 1000)
+    }
+
+    /// Subtracts a duration returning `None` when the result would be negative
+    ///
+    /// Unlike `Sub` implementation, which saturates at zero, allows to detect underflow.
+    pub fn checked_sub(&self, other: Duration) -> Option<Time> {
+        self.into_duration().checked_sub(other).map(Self::from_duration)
+    }
+
+    /// Returns signed difference between times in milliseconds
+    ///
+    /// The result is negative when `self` is before `other`.
+    pub fn diff(&self, other: &Time) -> i64 {
+        self.total_milliseconds() as i64 - other.total_milliseconds() as i64
+    }
+
+    /// Returns total number of seconds including fractional part
+    pub fn total_seconds(&self) -> f64 {
+        self.total_milliseconds() as f64 / 1000.0
+    }
+}
+
+impl From<Duration> for Time {
+    fn from(duration: Duration) -> Self {
+        Self::from_duration(duration)
+    }
+}
+
+impl From<Time> for Duration {
+    fn from(time: Time) -> Self {
+        time.into_duration()
+    }
+}
+
+impl Add<Duration> for Time {
+    type Output = Time;
+
+    fn add(self, rhs: Duration) -> Self::Output {
+        Self::from_duration(self.into_duration() + rhs)
+    }
+}
+
+impl Sub<Duration> for Time {
+    type Output = Time;
+
+    /// Subtracts a duration saturating at zero
+    fn sub(self, rhs: Duration) -> Self::Output {
+        Self::from_duration(self.into_duration().saturating_sub(rhs))
     }
 }
 
@@ -27,17 +148,25 @@ impl fmt::Display for Time {
     fn fmt(&self, out: &mut fmt::Formatter) -> fmt::Result {
         write!(
             out,
-            "{:02}:{:02}:{:02},{}",
+            "{:02}:{:02}:{:02},{:03}",
             self.hours, self.minutes, self.seconds, self.milliseconds
         )
     }
 }
 
+/// Parses time in `HH:MM:SS,mmm` format
+///
+/// Milliseconds are interpreted by digit count, so `00:00:01,5` means 1 second and 500 milliseconds,
+/// digits after the third one are truncated, e.g. `00:00:01,1234` means 1 second and 123 milliseconds.
 impl FromStr for Time {
     type Err = ParseTimeError;
 
     fn from_str(raw: &str) -> Result<Self, Self::Err> {
-        let mut raw = raw.trim().split(',');
+        let raw = raw.trim();
+        if raw.starts_with('-') {
+            return Err(ParseTimeError::NegativeTime);
+        }
+        let mut raw = raw.split(',');
         let (hours, minutes, seconds) = match raw.next() {
             Some(raw_time) => {
                 let mut raw_time = raw_time.split(':');
@@ -61,7 +190,7 @@ impl FromStr for Time {
             None => return Err(ParseTimeError::MissingTime),
         };
         let milliseconds = match raw.next() {
-            Some(value) => value.parse::<u64>().map_err(ParseTimeError::ParseMilliseconds)?,
+            Some(value) => parse_milliseconds(value)?,
             None => return Err(ParseTimeError::MissingMilliseconds),
         };
         if let Some(part) = raw.next() {
@@ -76,6 +205,91 @@ impl FromStr for Time {
     }
 }
 
+fn round(value: f64) -> u64 {
+    (value + 0.5) as u64
+}
+
+/// Parses fractional part of a second
+///
+/// Value is interpreted by digit count, so `5` and `50` mean 500 milliseconds,
+/// digits after the third one are truncated, e.g. `1234` means 123 milliseconds.
+fn parse_milliseconds(value: &str) -> Result<u64, ParseTimeError> {
+    parse_fraction(value, 3)
+}
+
+/// Parses fractional part of a second keeping given number of digits
+fn parse_fraction(value: &str, precision: usize) -> Result<u64, ParseTimeError> {
+    let digits = value.strip_prefix('+').unwrap_or(value);
+    if digits.is_empty() || !digits.bytes().all(|b| b.is_ascii_digit()) {
+        return value.parse::<u64>().map_err(ParseTimeError::ParseMilliseconds);
+    }
+    Ok(digits
+        .bytes()
+        .chain(iter::repeat(b'0'))
+        .take(precision)
+        .fold(0, |acc, b| acc * 10 + u64::from(b - b'0')))
+}
+
+/// Time with microsecond precision
+///
+/// SRT times are millisecond-based, this type keeps up to 6 fractional digits
+/// so high-precision sources can be round-tripped without loss.
+/// Parsing accepts the same format as [`Time`], digits after the sixth one are truncated.
+///
+/// ```
+/// use srtparse::TimePrecise;
+///
+/// let time: TimePrecise = "00:00:01,123456".parse().unwrap();
+/// assert_eq!(time.time.milliseconds, 123);
+/// assert_eq!(time.microseconds, 456);
+/// assert_eq!(time.to_string(), "00:00:01,123456");
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct TimePrecise {
+    /// Time truncated to milliseconds
+    pub time: Time,
+    /// Sub-millisecond remainder, less than 1000
+    pub microseconds: u64,
+}
+
+impl TimePrecise {
+    /// Converts time to `Duration` from standard library
+    pub fn into_duration(self) -> Duration {
+        self.time.into_duration() + Duration::from_micros(self.microseconds)
+    }
+}
+
+impl From<Time> for TimePrecise {
+    fn from(time: Time) -> Self {
+        Self { time, microseconds: 0 }
+    }
+}
+
+impl From<TimePrecise> for Duration {
+    fn from(time: TimePrecise) -> Self {
+        time.into_duration()
+    }
+}
+
+impl fmt::Display for TimePrecise {
+    fn fmt(&self, out: &mut fmt::Formatter) -> fmt::Result {
+        write!(out, "{}{:03}", self.time, self.microseconds)
+    }
+}
+
+impl FromStr for TimePrecise {
+    type Err = ParseTimeError;
+
+    fn from_str(raw: &str) -> Result<Self, Self::Err> {
+        let time = raw.parse::<Time>()?;
+        let microseconds = match raw.trim().split_once(',') {
+            Some((_, fraction)) => parse_fraction(fraction, 6)? % 1000,
+            None => 0,
+        };
+        Ok(Self { time, microseconds })
+    }
+}
+
 /// An error when parsing time
 #[derive(Debug)]
 pub enum ParseTimeError {
@@ -97,6 +311,8 @@ pub enum ParseTimeError {
     MissingSeconds,
     /// Time part is empty
     MissingTime,
+    /// Time starts with a minus sign
+    NegativeTime,
     /// Got an unexpected part of time
     UnexpectedTimePart(String),
 }
@@ -114,6 +330,7 @@ impl fmt::Display for ParseTimeError {
             MissingSeconds => write!(out, "seconds not found"),
             MissingMilliseconds => write!(out, "milliseconds not found"),
             MissingTime => write!(out, "time not found"),
+            NegativeTime => write!(out, "time can not be negative"),
             UnexpectedTimePart(part) => write!(out, "unexpected time part: '{part}'"),
         }
     }
@@ -160,6 +377,58 @@ mod tests {
         );
     }
 
+    #[test]
+    fn parse_flexible() {
+        assert_eq!(
+            Time::parse_flexible("01:02,500").unwrap(),
+            Time {
+                hours: 0,
+                minutes: 1,
+                seconds: 2,
+                milliseconds: 500
+            }
+        );
+        assert_eq!(
+            Time::parse_flexible("01:02:03,500").unwrap(),
+            Time {
+                hours: 1,
+                minutes: 2,
+                seconds: 3,
+                milliseconds: 500
+            }
+        );
+        assert_eq!(
+            "01:02,500".parse::<Time>().unwrap_err().to_string(),
+            "seconds not found"
+        );
+        assert_eq!(
+            Time::parse_flexible("x:02,500").unwrap_err().to_string(),
+            "could not parse minutes: invalid digit found in string"
+        );
+    }
+
+    #[test]
+    fn parse_fraction() {
+        for (raw, milliseconds) in [
+            ("00:00:01,5", 500),
+            ("00:00:01,50", 500),
+            ("00:00:01,500", 500),
+            ("00:00:01,05", 50),
+            ("00:00:01,005", 5),
+            ("00:00:01,1234", 123),
+        ] {
+            assert_eq!(raw.parse::<Time>().unwrap().milliseconds, milliseconds, "{raw}");
+        }
+        assert_eq!(
+            "00:00:01,".parse::<Time>().unwrap_err().to_string(),
+            "could not parse milliseconds: cannot parse integer from empty string"
+        );
+        assert_eq!(
+            "00:00:01,-5".parse::<Time>().unwrap_err().to_string(),
+            "could not parse milliseconds: invalid digit found in string"
+        );
+    }
+
     #[test]
     fn display() {
         let time = Time {
@@ -169,6 +438,13 @@ mod tests {
             milliseconds: 200,
         };
         assert_eq!(time.to_string(), "00:01:02,200");
+        let time = Time {
+            hours: 0,
+            minutes: 1,
+            seconds: 2,
+            milliseconds: 5,
+        };
+        assert_eq!(time.to_string(), "00:01:02,005");
     }
 
     #[test]
@@ -181,4 +457,226 @@ mod tests {
         };
         assert_eq!(time.into_duration(), Duration::from_millis(62200));
     }
+
+    #[test]
+    fn from_duration() {
+        assert_eq!(
+            Time::from_duration(Duration::from_micros(3_662_250_999)),
+            Time {
+                hours: 1,
+                minutes: 1,
+                seconds: 2,
+                milliseconds: 250,
+            }
+        );
+    }
+
+    #[test]
+    fn add_sub() {
+        let time = Time {
+            hours: 0,
+            minutes: 0,
+            seconds: 59,
+            milliseconds: 200,
+        };
+        assert_eq!(
+            time + Duration::from_secs(2),
+            Time {
+                hours: 0,
+                minutes: 1,
+                seconds: 1,
+                milliseconds: 200,
+            }
+        );
+        assert_eq!(
+            time - Duration::from_millis(500),
+            Time {
+                hours: 0,
+                minutes: 0,
+                seconds: 58,
+                milliseconds: 700,
+            }
+        );
+        assert_eq!(time - Duration::from_secs(60), Time::from_duration(Duration::ZERO));
+    }
+
+    #[test]
+    fn ordering() {
+        let a = Time {
+            hours: 0,
+            minutes: 59,
+            seconds: 59,
+            milliseconds: 999,
+        };
+        let b = Time {
+            hours: 1,
+            minutes: 0,
+            seconds: 0,
+            milliseconds: 0,
+        };
+        assert!(a < b);
+        assert_eq!(a.max(b), b);
+    }
+
+    #[test]
+    fn totals() {
+        let time = Time {
+            hours: 1,
+            minutes: 1,
+            seconds: 2,
+            milliseconds: 250,
+        };
+        assert_eq!(time.total_milliseconds(), 3_662_250);
+        assert_eq!(time.total_seconds(), 3662.25);
+    }
+
+    #[test]
+    fn normalized() {
+        let time = |hours, minutes, seconds, milliseconds| Time {
+            hours,
+            minutes,
+            seconds,
+            milliseconds,
+        };
+        assert_eq!(time(0, 0, 0, 1500).normalized(), time(0, 0, 1, 500));
+        assert_eq!(time(0, 0, 90, 0).normalized(), time(0, 1, 30, 0));
+        assert_eq!(time(1, 59, 59, 1000).normalized(), time(2, 0, 0, 0));
+        assert_eq!(time(0, 75, 0, 0).normalized(), time(1, 15, 0, 0));
+        assert_eq!(time(0, 0, 0, 1500).normalized().to_string(), "00:00:01,500");
+    }
+
+    #[test]
+    fn frames() {
+        let ms = |value| Time::from_duration(Duration::from_millis(value));
+        assert_eq!(Time::from_frames(0, 25.0), ms(0));
+        assert_eq!(Time::from_frames(25, 25.0), ms(1000));
+        assert_eq!(Time::from_frames(48, 24.0), ms(2000));
+        assert_eq!(Time::from_frames(30, 30.0), ms(1000));
+        // 30 / 29.97 = 1.001001s
+        assert_eq!(Time::from_frames(30, 29.97), ms(1001));
+        // 24 / 23.976 = 1.001001s
+        assert_eq!(Time::from_frames(24, 23.976), ms(1001));
+        assert_eq!(ms(1000).to_frames(25.0), 25);
+        assert_eq!(ms(1001).to_frames(29.97), 30);
+        assert_eq!(ms(1019).to_frames(25.0), 25);
+        assert_eq!(ms(1021).to_frames(25.0), 26);
+        for fps in [23.976, 24.0, 25.0, 29.97, 30.0] {
+            for frame in [0, 1, 17, 1000, 123_456] {
+                assert_eq!(Time::from_frames(frame, fps).to_frames(fps), frame);
+            }
+        }
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic(expected = "frame rate must be positive and finite")]
+    fn frames_zero_fps() {
+        Time::from_frames(10, 0.0);
+    }
+
+    #[test]
+    fn hash() {
+        let time = Time {
+            hours: 0,
+            minutes: 1,
+            seconds: 2,
+            milliseconds: 3,
+        };
+        let set = std::collections::HashSet::from([time, Time::from_duration(time.into_duration())]);
+        assert_eq!(set.len(), 1);
+    }
+
+    #[test]
+    fn diff() {
+        let early = Time {
+            hours: 0,
+            minutes: 1,
+            seconds: 2,
+            milliseconds: 500,
+        };
+        let late = Time {
+            hours: 1,
+            minutes: 0,
+            seconds: 0,
+            milliseconds: 0,
+        };
+        assert_eq!(late.diff(&early), 3_537_500);
+        assert_eq!(early.diff(&late), -3_537_500);
+        assert_eq!(early.diff(&early), 0);
+    }
+
+    #[test]
+    fn from_into_duration() {
+        let duration = Duration::from_micros(3_662_250_999);
+        let time = Time::from(duration);
+        assert_eq!(time, Time::from_duration(duration));
+        assert_eq!(Duration::from(time), time.into_duration());
+        let duration: Duration = time.into();
+        assert_eq!(duration, Duration::from_millis(3_662_250));
+    }
+
+    #[test]
+    fn parse_negative() {
+        let err = "-00:00:01,000".parse::<Time>().unwrap_err();
+        assert!(matches!(err, ParseTimeError::NegativeTime));
+        assert_eq!(err.to_string(), "time can not be negative");
+        assert!(matches!(
+            " -00:00:01,000".parse::<Time>(),
+            Err(ParseTimeError::NegativeTime)
+        ));
+        assert!(matches!(
+            Time::parse_flexible("-01:00,000"),
+            Err(ParseTimeError::NegativeTime)
+        ));
+    }
+
+    #[test]
+    fn precise() {
+        let time: TimePrecise = "00:00:01,123456".parse().unwrap();
+        assert_eq!(
+            time,
+            TimePrecise {
+                time: Time {
+                    hours: 0,
+                    minutes: 0,
+                    seconds: 1,
+                    milliseconds: 123,
+                },
+                microseconds: 456,
+            }
+        );
+        assert_eq!(time.to_string(), "00:00:01,123456");
+        assert_eq!(time.into_duration(), Duration::from_micros(1_123_456));
+
+        let time: TimePrecise = "01:02:03,5".parse().unwrap();
+        assert_eq!(time.microseconds, 0);
+        assert_eq!(time.to_string(), "01:02:03,500000");
+        assert_eq!("00:00:01,1234567".parse::<TimePrecise>().unwrap().microseconds, 456);
+        assert!("00:00:01".parse::<TimePrecise>().is_err());
+        assert_eq!(TimePrecise::from(time.time), time);
+    }
+
+    #[test]
+    fn checked_sub() {
+        let time = Time {
+            hours: 0,
+            minutes: 1,
+            seconds: 0,
+            milliseconds: 500,
+        };
+        assert_eq!(
+            time.checked_sub(Duration::from_millis(1000)),
+            Some(Time {
+                hours: 0,
+                minutes: 0,
+                seconds: 59,
+                milliseconds: 500,
+            })
+        );
+        assert_eq!(
+            time.checked_sub(Duration::from_millis(60_500)),
+            Some(Time::from_duration(Duration::ZERO))
+        );
+        assert_eq!(time.checked_sub(Duration::from_millis(60_501)), None);
+    }
 }
@@ -0,0 +1,176 @@
+use crate::item::Item;
+use alloc::vec::Vec;
+use core::{fmt, time::Duration};
+
+/// A problem found in subtitles
+///
+/// Each variant carries an index of the offending subtitle in the given slice.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ValidationIssue {
+    /// Position is not greater than position of the previous subtitle
+    NonMonotonicPosition(usize),
+    /// End time is before start time
+    ReversedTimes(usize),
+    /// Subtitle starts before the previous one ends
+    Overlap(usize),
+    /// Start time is equal to end time
+    ZeroDuration(usize),
+    /// Text is empty or contains whitespaces only
+    EmptyText(usize),
+}
+
+impl fmt::Display for ValidationIssue {
+    fn fmt(&self, out: &mut fmt::Formatter) -> fmt::Result {
+        use self::ValidationIssue::*;
+        match self {
+            NonMonotonicPosition(idx) => write!(out, "item #{idx} has non-monotonic position"),
+            ReversedTimes(idx) => write!(out, "item #{idx} ends before it starts"),
+            Overlap(idx) => write!(out, "item #{idx} overlaps with the previous one"),
+            ZeroDuration(idx) => write!(out, "item #{idx} has zero duration"),
+            EmptyText(idx) => write!(out, "item #{idx} has empty text"),
+        }
+    }
+}
+
+/// Checks subtitles and returns all found problems
+pub fn validate(items: &[Item]) -> Vec<ValidationIssue> {
+    use self::ValidationIssue::*;
+    let mut result = Vec::new();
+    for (idx, item) in items.iter().enumerate() {
+        let start_time = item.start_time.into_duration();
+        let end_time = item.end_time.into_duration();
+        if idx > 0 {
+            let prev = &items[idx - 1];
+            if item.pos <= prev.pos {
+                result.push(NonMonotonicPosition(idx));
+            }
+            if start_time < prev.end_time.into_duration() {
+                result.push(Overlap(idx));
+            }
+        }
+        if end_time < start_time {
+            result.push(ReversedTimes(idx));
+        } else if is_zero_duration(item) {
+            result.push(ZeroDuration(idx));
+        }
+        if item.text.trim().is_empty() {
+            result.push(EmptyText(idx));
+        }
+    }
+    result
+}
+
+/// Returns indices of subtitles which start time is equal to end time
+pub fn find_zero_duration(items: &[Item]) -> Vec<usize> {
+    items
+        .iter()
+        .enumerate()
+        .filter_map(|(idx, item)| is_zero_duration(item).then_some(idx))
+        .collect()
+}
+
+/// Returns indices of subtitles which text has more than `max_lines` lines
+pub fn find_too_many_lines(items: &[Item], max_lines: usize) -> Vec<usize> {
+    items
+        .iter()
+        .enumerate()
+        .filter_map(|(idx, item)| (item.text.split('\n').count() > max_lines).then_some(idx))
+        .collect()
+}
+
+/// Returns indices of subtitles displayed longer than `max`
+pub fn find_too_long(items: &[Item], max: Duration) -> Vec<usize> {
+    items
+        .iter()
+        .enumerate()
+        .filter_map(|(idx, item)| (item.duration() > max).then_some(idx))
+        .collect()
+}
+
+pub(crate) fn is_zero_duration(item: &Item) -> bool {
+    item.start_time.into_duration() == item.end_time.into_duration()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::time::Time;
+
+    fn time(seconds: u64) -> Time {
+        Time {
+            hours: 0,
+            minutes: 0,
+            seconds,
+            milliseconds: 0,
+        }
+    }
+
+    fn item(pos: usize, start: u64, end: u64, text: &str) -> Item {
+        Item {
+            pos,
+            start_time: time(start),
+            end_time: time(end),
+            text: String::from(text),
+            label: None,
+            coordinates: None,
+        }
+    }
+
+    #[test]
+    fn valid() {
+        let items = vec![item(1, 1, 2, "a"), item(2, 2, 3, "b"), item(5, 10, 12, "c")];
+        assert!(validate(&items).is_empty());
+    }
+
+    #[test]
+    fn invalid() {
+        let items = vec![
+            item(1, 1, 2, "a"),
+            item(1, 3, 4, "b"),
+            item(3, 6, 5, "c"),
+            item(4, 7, 7, "d"),
+            item(5, 8, 9, " "),
+            item(6, 8, 10, "e"),
+        ];
+        assert_eq!(
+            validate(&items),
+            vec![
+                ValidationIssue::NonMonotonicPosition(1),
+                ValidationIssue::ReversedTimes(2),
+                ValidationIssue::ZeroDuration(3),
+                ValidationIssue::EmptyText(4),
+                ValidationIssue::Overlap(5),
+            ]
+        );
+        assert_eq!(
+            ValidationIssue::Overlap(5).to_string(),
+            "item #5 overlaps with the previous one"
+        );
+    }
+
+    #[test]
+    fn zero_duration() {
+        let items = vec![item(1, 1, 2, "a"), item(2, 3, 3, "b"), item(3, 4, 5, "c")];
+        assert_eq!(find_zero_duration(&items), vec![1]);
+        assert!(find_zero_duration(&items[2..]).is_empty());
+    }
+
+    #[test]
+    fn too_many_lines() {
+        let items = vec![
+            item(1, 1, 2, "one"),
+            item(2, 3, 4, "one\ntwo"),
+            item(3, 5, 6, "one\ntwo\nthree"),
+        ];
+        assert_eq!(find_too_many_lines(&items, 2), vec![2]);
+        assert_eq!(find_too_many_lines(&items, 1), vec![1, 2]);
+        assert!(find_too_many_lines(&items, 3).is_empty());
+    }
+
+    #[test]
+    fn too_long() {
+        let items = vec![item(1, 0, 5, "a"), item(2, 6, 18, "b"), item(3, 20, 27, "c")];
+        assert_eq!(find_too_long(&items, Duration::from_secs(7)), vec![1]);
+        assert!(find_too_long(&items, Duration::from_secs(12)).is_empty());
+    }
+}
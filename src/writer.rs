@@ -0,0 +1,142 @@
+use crate::item::Item;
+use alloc::string::{String, ToString};
+use core::fmt;
+#[cfg(feature = "std")]
+use std::io::{Result as IoResult, Write};
+
+/// Displays subtitles in SRT format
+///
+/// ```
+/// let items = srtparse::from_str("1\n00:00:01,000 --> 00:00:02,000\nHello!").unwrap();
+/// println!("{}", srtparse::SrtDisplay(&items));
+/// ```
+#[derive(Clone, Copy, Debug)]
+pub struct SrtDisplay<'a>(pub &'a [Item]);
+
+impl fmt::Display for SrtDisplay<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for (idx, item) in self.0.iter().enumerate() {
+            if idx > 0 {
+                f.write_str("\n\n")?;
+            }
+            write!(f, "{}", item)?;
+        }
+        if !self.0.is_empty() {
+            f.write_str("\n")?;
+        }
+        Ok(())
+    }
+}
+
+/// Line terminator used when writing subtitles
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum LineEnding {
+    /// `\n`
+    #[default]
+    Lf,
+    /// `\r\n`, expected by some Windows and hardware players
+    Crlf,
+}
+
+/// Write subtitles to a string
+///
+/// Positions are written as is, call [`renumber`](crate::renumber) beforehand to make them sequential.
+pub fn to_string(items: &[Item]) -> String {
+    SrtDisplay(items).to_string()
+}
+
+/// Write subtitles to a string using given line terminator
+///
+/// Line breaks within subtitle text are converted as well, including the ones which are `\r\n` already.
+pub fn to_string_with(items: &[Item], line_ending: LineEnding) -> String {
+    let result = to_string(items);
+    match line_ending {
+        LineEnding::Lf => result,
+        LineEnding::Crlf => result.replace("\r\n", "\n").replace('\n', "\r\n"),
+    }
+}
+
+/// Write subtitles to a writer
+#[cfg(feature = "std")]
+pub fn to_writer(writer: impl Write, items: &[Item]) -> IoResult<()> {
+    to_writer_with(writer, items, LineEnding::Lf)
+}
+
+/// Write subtitles to a writer using given line terminator
+#[cfg(feature = "std")]
+pub fn to_writer_with(mut writer: impl Write, items: &[Item], line_ending: LineEnding) -> IoResult<()> {
+    writer.write_all(to_string_with(items, line_ending).as_bytes())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::reader::from_str;
+
+    const SOURCE: &str = "1\n00:00:01,000-->00:00:02,000\nHello\n\n2\n00:00:03,000-->00:00:04,500\nfirst\nsecond\n";
+
+    #[test]
+    fn write_string() {
+        let items = from_str(SOURCE).unwrap();
+        assert_eq!(to_string(&items), SOURCE);
+        assert_eq!(to_string(&[]), "");
+    }
+
+    #[test]
+    fn display() {
+        let items = from_str(SOURCE).unwrap();
+        assert_eq!(format!("{}", SrtDisplay(&items)), SOURCE);
+        assert_eq!(format!("{}", SrtDisplay(&[])), "");
+    }
+
+    #[test]
+    fn write_writer() {
+        let items = from_str(SOURCE).unwrap();
+        let mut buf = Vec::new();
+        to_writer(&mut buf, &items).unwrap();
+        assert_eq!(buf, SOURCE.as_bytes());
+    }
+
+    #[test]
+    fn write_preserves_positions() {
+        let source = "1\n00:00:01,000-->00:00:02,000\na\n\n3\n00:00:03,000-->00:00:04,000\nb\n\n7\n00:00:05,000-->00:00:06,000\nc\n";
+        let mut items = from_str(source).unwrap();
+        assert_eq!(to_string(&items), source);
+        crate::edit::renumber(&mut items);
+        let positions = to_string(&items)
+            .split("\n\n")
+            .map(|block| block.lines().next().unwrap().to_owned())
+            .collect::<Vec<_>>();
+        assert_eq!(positions, vec!["1", "2", "3"]);
+    }
+
+    #[test]
+    fn write_normalized_position() {
+        let items = from_str(" 007 \n00:00:01,000-->00:00:02,000\nHello\n").unwrap();
+        assert_eq!(items[0].pos, 7);
+        assert_eq!(to_string(&items), "7\n00:00:01,000-->00:00:02,000\nHello\n");
+    }
+
+    #[test]
+    fn write_crlf() {
+        let items = from_str(SOURCE).unwrap();
+        let expected =
+            "1\r\n00:00:01,000-->00:00:02,000\r\nHello\r\n\r\n2\r\n00:00:03,000-->00:00:04,500\r\nfirst\r\nsecond\r\n";
+        assert_eq!(to_string_with(&items, LineEnding::Crlf), expected);
+        assert!(!expected.replace("\r\n", "").contains('\n'));
+        assert_eq!(to_string_with(&items, LineEnding::default()), SOURCE);
+        let mut buf = Vec::new();
+        to_writer_with(&mut buf, &items, LineEnding::Crlf).unwrap();
+        assert_eq!(buf, expected.as_bytes());
+        assert_eq!(from_str(expected).unwrap(), items);
+    }
+
+    #[test]
+    fn write_crlf_text() {
+        let mut items = from_str(SOURCE).unwrap();
+        items[1].text = String::from("first\r\nsecond");
+        let expected =
+            "1\r\n00:00:01,000-->00:00:02,000\r\nHello\r\n\r\n2\r\n00:00:03,000-->00:00:04,500\r\nfirst\r\nsecond\r\n";
+        assert_eq!(to_string_with(&items, LineEnding::Crlf), expected);
+    }
+}